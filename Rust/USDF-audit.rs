@@ -0,0 +1,65 @@
+use cosmwasm_std::{Addr, Attribute, Storage, Timestamp};
+use cosmwasm_storage::{
+    bucket, bucket_read, singleton, singleton_read, Bucket, ReadonlyBucket, ReadonlySingleton,
+    Singleton,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+pub static AUDIT_SEQ_KEY: &[u8] = b"audit_seq";
+pub static AUDIT_LOG_KEY: &[u8] = b"audit_log";
+pub static AUDIT_MEMBER_INDEX_KEY: &[u8] = b"audit_member";
+pub static AUDIT_ACTION_INDEX_KEY: &[u8] = b"audit_action";
+
+/// An audited action, with the response attributes it was executed with.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AuditEntry {
+    pub id: u64,
+    pub height: u64,
+    pub time: Timestamp,
+    pub sender: Addr,
+    pub action: String,
+    pub member: Option<String>,
+    pub params: Vec<Attribute>,
+}
+
+pub fn audit_seq(storage: &mut dyn Storage) -> Singleton<u64> {
+    singleton(storage, AUDIT_SEQ_KEY)
+}
+
+pub fn audit_seq_read(storage: &dyn Storage) -> ReadonlySingleton<u64> {
+    singleton_read(storage, AUDIT_SEQ_KEY)
+}
+
+// Entries keyed by big-endian entry id.
+pub fn audit_log(storage: &mut dyn Storage) -> Bucket<AuditEntry> {
+    bucket(storage, AUDIT_LOG_KEY)
+}
+
+pub fn audit_log_read(storage: &dyn Storage) -> ReadonlyBucket<AuditEntry> {
+    bucket_read(storage, AUDIT_LOG_KEY)
+}
+
+// Entry ids for a member, keyed by big-endian entry id.
+pub fn audit_member_index<'a>(storage: &'a mut dyn Storage, member: &str) -> Bucket<'a, u64> {
+    Bucket::multilevel(storage, &[AUDIT_MEMBER_INDEX_KEY, member.as_bytes()])
+}
+
+pub fn audit_member_index_read<'a>(
+    storage: &'a dyn Storage,
+    member: &str,
+) -> ReadonlyBucket<'a, u64> {
+    ReadonlyBucket::multilevel(storage, &[AUDIT_MEMBER_INDEX_KEY, member.as_bytes()])
+}
+
+// Entry ids for an action, keyed by big-endian entry id.
+pub fn audit_action_index<'a>(storage: &'a mut dyn Storage, action: &str) -> Bucket<'a, u64> {
+    Bucket::multilevel(storage, &[AUDIT_ACTION_INDEX_KEY, action.as_bytes()])
+}
+
+pub fn audit_action_index_read<'a>(
+    storage: &'a dyn Storage,
+    action: &str,
+) -> ReadonlyBucket<'a, u64> {
+    ReadonlyBucket::multilevel(storage, &[AUDIT_ACTION_INDEX_KEY, action.as_bytes()])
+}
@@ -0,0 +1,75 @@
+use cosmwasm_std::{Addr, Storage, Uint128};
+use cosmwasm_storage::{
+    bucket, bucket_read, singleton, singleton_read, Bucket, ReadonlyBucket, ReadonlySingleton,
+    Singleton,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+pub static SNAPSHOT_SEQ_KEY: &[u8] = b"snapshot_seq";
+pub static SNAPSHOT_KEY: &[u8] = b"snapshot";
+pub static DISTRIBUTION_SEQ_KEY: &[u8] = b"distribution_seq";
+pub static DISTRIBUTION_KEY: &[u8] = b"distribution";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HolderBalance {
+    pub address: Addr,
+    pub amount: Uint128,
+}
+
+/// The balances of a member's customers at a block height.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BalanceSnapshot {
+    pub id: u64,
+    pub member: Addr,
+    pub height: u64,
+    pub balances: Vec<HolderBalance>,
+    pub total: Uint128,
+    // The distribution made from the snapshot, once there is one.
+    pub distribution: Option<u64>,
+}
+
+/// Yield minted by a member and paid out to the holders of a snapshot.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct YieldDistribution {
+    pub id: u64,
+    pub snapshot_id: u64,
+    pub member: Addr,
+    pub amount: Uint128,
+    pub height: u64,
+    pub payouts: Vec<HolderBalance>,
+}
+
+pub fn snapshot_seq(storage: &mut dyn Storage) -> Singleton<u64> {
+    singleton(storage, SNAPSHOT_SEQ_KEY)
+}
+
+pub fn snapshot_seq_read(storage: &dyn Storage) -> ReadonlySingleton<u64> {
+    singleton_read(storage, SNAPSHOT_SEQ_KEY)
+}
+
+// Snapshots keyed by big-endian snapshot id.
+pub fn snapshots(storage: &mut dyn Storage) -> Bucket<BalanceSnapshot> {
+    bucket(storage, SNAPSHOT_KEY)
+}
+
+pub fn snapshots_read(storage: &dyn Storage) -> ReadonlyBucket<BalanceSnapshot> {
+    bucket_read(storage, SNAPSHOT_KEY)
+}
+
+pub fn distribution_seq(storage: &mut dyn Storage) -> Singleton<u64> {
+    singleton(storage, DISTRIBUTION_SEQ_KEY)
+}
+
+pub fn distribution_seq_read(storage: &dyn Storage) -> ReadonlySingleton<u64> {
+    singleton_read(storage, DISTRIBUTION_SEQ_KEY)
+}
+
+// Distributions keyed by big-endian distribution id.
+pub fn distributions(storage: &mut dyn Storage) -> Bucket<YieldDistribution> {
+    bucket(storage, DISTRIBUTION_KEY)
+}
+
+pub fn distributions_read(storage: &dyn Storage) -> ReadonlyBucket<YieldDistribution> {
+    bucket_read(storage, DISTRIBUTION_KEY)
+}
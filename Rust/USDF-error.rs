@@ -0,0 +1,278 @@
+use cosmwasm_std::{StdError, Uint128};
+use thiserror::Error;
+
+// Each message is prefixed with the stable numeric code returned by `code`.
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("[1] {0}")]
+    Std(#[from] StdError),
+
+    #[error("[2] Semver parsing error: {0}")]
+    SemVer(String),
+
+    // General
+    #[error("[1000] Unauthorized")]
+    Unauthorized {},
+
+    #[error("[1001] Funds are not allowed for {operation}")]
+    FundsNotAllowed { operation: String },
+
+    #[error("[1002] Invalid amount for {operation}")]
+    InvalidAmount { operation: String },
+
+    #[error("[1003] Insufficient balance for {operation}")]
+    InsufficientBalance { operation: String },
+
+    #[error("[1004] Contract is paused for {operation}")]
+    Paused { operation: String },
+
+    #[error("[1005] Missing field: {field}")]
+    MissingField { field: String },
+
+    #[error("[1006] Invalid fields: {fields:?}")]
+    InvalidFields { fields: Vec<String> },
+
+    #[error("[1007] Duplicate entries in {field}")]
+    DuplicateEntries { field: String },
+
+    // Membership
+    #[error("[2000] Member not found")]
+    MemberNotFound {},
+
+    #[error("[2001] Member already exists")]
+    DuplicateMember {},
+
+    #[error("[2002] Member is winding down")]
+    MemberExiting {},
+
+    #[error("[2003] Member has outstanding supply")]
+    MemberHasOutstandingSupply {},
+
+    #[error("[2004] Member has no kyc attributes")]
+    MemberMissingKyc {},
+
+    #[error("[2005] No members")]
+    NoMembers {},
+
+    // Kyc
+    #[error("[3000] No kyc attribute found for {address}")]
+    KycNotFound { address: String },
+
+    #[error("[3001] Kyc attribute already exists")]
+    KycAttributeExists {},
+
+    #[error("[3002] Kyc attribute not found")]
+    KycAttributeNotFound {},
+
+    #[error("[3003] Kyc not verified for {address}")]
+    KycNotVerified { address: String },
+
+    #[error("[3004] Kyc verification lapsed for {address}")]
+    KycLapsed { address: String },
+
+    // Transfers and executors
+    #[error("[4000] Account is frozen: {address}")]
+    AccountFrozen { address: String },
+
+    #[error("[4001] Account is not frozen")]
+    AccountNotFrozen {},
+
+    #[error("[4002] Account is already frozen")]
+    AccountAlreadyFrozen {},
+
+    #[error("[4003] Cannot freeze the contract account")]
+    CannotFreezeContract {},
+
+    #[error("[4010] Executor already exists")]
+    ExecutorExists {},
+
+    #[error("[4011] Executor not found")]
+    ExecutorNotFound {},
+
+    #[error("[4012] No executor allowance")]
+    NoExecutorAllowance {},
+
+    #[error("[4013] Executor allowance expired")]
+    ExecutorAllowanceExpired {},
+
+    #[error("[4014] Source account not allowed for executor")]
+    SourceNotAllowed {},
+
+    #[error("[4015] Exceeds executor transaction limit")]
+    ExceedsExecutorTxLimit {},
+
+    #[error("[4016] Exceeds executor period limit")]
+    ExceedsExecutorPeriodLimit {},
+
+    #[error("[4020] Velocity limit of {limit} per {window_seconds}s exceeded for {address}")]
+    VelocityLimitExceeded {
+        address: String,
+        limit: Uint128,
+        window_seconds: u64,
+    },
+
+    #[error("[4021] Velocity tier not found")]
+    VelocityTierNotFound {},
+
+    #[error("[4030] Ibc channel not found: {channel_id}")]
+    IbcChannelNotFound { channel_id: String },
+
+    #[error("[4031] Ibc receiver not allowed: {receiver}")]
+    IbcReceiverNotAllowed { receiver: String },
+
+    #[error("[4032] Invalid ibc channel")]
+    InvalidIbcChannel {},
+
+    #[error("[4033] Ibc channels cannot be closed")]
+    ChannelCloseNotAllowed {},
+
+    // Supply
+    #[error("[5000] Exceeds attested reserves")]
+    ExceedsReserves {},
+
+    #[error("[5001] Exceeds member supply")]
+    ExceedsMemberSupply {},
+
+    #[error("[5010] Proposal already approved")]
+    ProposalAlreadyApproved {},
+
+    #[error("[5011] Quorum not reached")]
+    QuorumNotReached {},
+
+    #[error("[5012] Approvers not set")]
+    ApproversNotSet {},
+
+    #[error("[5020] Duplicate request")]
+    DuplicateRequest {},
+
+    #[error("[5021] Request is not pending")]
+    RequestNotPending {},
+
+    #[error("[5022] Request expired")]
+    RequestExpired {},
+
+    #[error("[5023] Request has not expired")]
+    RequestNotExpired {},
+
+    #[error("[5030] Snapshot is empty")]
+    EmptySnapshot {},
+
+    #[error("[5031] Snapshot already distributed")]
+    SnapshotDistributed {},
+
+    #[error("[5040] Marker not found: {denom}")]
+    MarkerNotFound { denom: String },
+
+    #[error("[5041] Invalid marker account: {denom}")]
+    InvalidMarker { denom: String },
+
+    // Administration
+    #[error("[6000] No pending admin")]
+    NoPendingAdmin {},
+
+    #[error("[6001] Admin unchanged")]
+    AdminUnchanged {},
+
+    #[error("[6002] Role already granted")]
+    RoleAlreadyGranted {},
+
+    #[error("[6003] Role not granted")]
+    RoleNotGranted {},
+
+    #[error("[6004] Recovery account not set")]
+    RecoveryAccountNotSet {},
+
+    #[error("[6010] Invalid contract name")]
+    InvalidContractName {},
+
+    #[error("[6011] Invalid contract version")]
+    InvalidContractVersion {},
+
+    #[error("[6012] Migration dry run: {plan}")]
+    MigrationDryRun { plan: String },
+
+    #[error("[6013] Upgrade not authorized")]
+    UpgradeNotAuthorized {},
+}
+
+impl From<semver::Error> for ContractError {
+    fn from(err: semver::Error) -> Self {
+        Self::SemVer(err.to_string())
+    }
+}
+
+impl ContractError {
+    // Stable numeric error codes, so clients need not match on error messages.
+    // Codes are grouped by area and must never be reused or renumbered.
+    pub fn code(&self) -> u32 {
+        match self {
+            // Standard library and dependency errors
+            ContractError::Std(_) => 1,
+            ContractError::SemVer(_) => 2,
+            // General
+            ContractError::Unauthorized {} => 1000,
+            ContractError::FundsNotAllowed { .. } => 1001,
+            ContractError::InvalidAmount { .. } => 1002,
+            ContractError::InsufficientBalance { .. } => 1003,
+            ContractError::Paused { .. } => 1004,
+            ContractError::MissingField { .. } => 1005,
+            ContractError::InvalidFields { .. } => 1006,
+            ContractError::DuplicateEntries { .. } => 1007,
+            // Membership
+            ContractError::MemberNotFound {} => 2000,
+            ContractError::DuplicateMember {} => 2001,
+            ContractError::MemberExiting {} => 2002,
+            ContractError::MemberHasOutstandingSupply {} => 2003,
+            ContractError::MemberMissingKyc {} => 2004,
+            ContractError::NoMembers {} => 2005,
+            // Kyc
+            ContractError::KycNotFound { .. } => 3000,
+            ContractError::KycAttributeExists {} => 3001,
+            ContractError::KycAttributeNotFound {} => 3002,
+            ContractError::KycNotVerified { .. } => 3003,
+            ContractError::KycLapsed { .. } => 3004,
+            // Transfers and executors
+            ContractError::AccountFrozen { .. } => 4000,
+            ContractError::AccountNotFrozen {} => 4001,
+            ContractError::AccountAlreadyFrozen {} => 4002,
+            ContractError::CannotFreezeContract {} => 4003,
+            ContractError::ExecutorExists {} => 4010,
+            ContractError::ExecutorNotFound {} => 4011,
+            ContractError::NoExecutorAllowance {} => 4012,
+            ContractError::ExecutorAllowanceExpired {} => 4013,
+            ContractError::SourceNotAllowed {} => 4014,
+            ContractError::ExceedsExecutorTxLimit {} => 4015,
+            ContractError::ExceedsExecutorPeriodLimit {} => 4016,
+            ContractError::VelocityLimitExceeded { .. } => 4020,
+            ContractError::VelocityTierNotFound {} => 4021,
+            ContractError::IbcChannelNotFound { .. } => 4030,
+            ContractError::IbcReceiverNotAllowed { .. } => 4031,
+            ContractError::InvalidIbcChannel {} => 4032,
+            ContractError::ChannelCloseNotAllowed {} => 4033,
+            // Supply
+            ContractError::ExceedsReserves {} => 5000,
+            ContractError::ExceedsMemberSupply {} => 5001,
+            ContractError::ProposalAlreadyApproved {} => 5010,
+            ContractError::QuorumNotReached {} => 5011,
+            ContractError::ApproversNotSet {} => 5012,
+            ContractError::DuplicateRequest {} => 5020,
+            ContractError::RequestNotPending {} => 5021,
+            ContractError::RequestExpired {} => 5022,
+            ContractError::RequestNotExpired {} => 5023,
+            ContractError::EmptySnapshot {} => 5030,
+            ContractError::SnapshotDistributed {} => 5031,
+            ContractError::MarkerNotFound { .. } => 5040,
+            ContractError::InvalidMarker { .. } => 5041,
+            // Administration
+            ContractError::NoPendingAdmin {} => 6000,
+            ContractError::AdminUnchanged {} => 6001,
+            ContractError::RoleAlreadyGranted {} => 6002,
+            ContractError::RoleNotGranted {} => 6003,
+            ContractError::RecoveryAccountNotSet {} => 6004,
+            ContractError::InvalidContractName {} => 6010,
+            ContractError::InvalidContractVersion {} => 6011,
+            ContractError::MigrationDryRun { .. } => 6012,
+            ContractError::UpgradeNotAuthorized {} => 6013,
+        }
+    }
+}
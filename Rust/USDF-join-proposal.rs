@@ -0,0 +1,58 @@
+use cosmwasm_std::{Addr, DepsMut, Order, StdResult, Storage, Uint128};
+use cosmwasm_storage::{bucket, bucket_read, Bucket, ReadonlyBucket};
+use schemars::JsonSchema;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+
+use crate::error::ContractError;
+#[allow(deprecated)]
+use crate::msg::{MigrateMsg, VoteChoice};
+
+pub static JOIN_PROPOSAL_KEY: &[u8] = b"proposal";
+
+/// A vote on admitting a member, from before members were admitted by the admin.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[deprecated(since = "0.5.0")]
+#[allow(deprecated)]
+pub struct JoinProposal {
+    pub id: Addr,
+    pub max_supply: Uint128,
+    pub denom: String,
+    pub created: Uint128,
+    pub expires: Uint128,
+    pub no: Uint128,
+    pub yes: Uint128,
+    pub voters: Vec<Addr>,
+    pub name: Option<String>,
+    pub admin_vote: Option<VoteChoice>,
+}
+
+#[deprecated(since = "0.5.0")]
+#[allow(deprecated)]
+pub fn legacy_join_proposals(storage: &mut dyn Storage) -> Bucket<JoinProposal> {
+    bucket(storage, JOIN_PROPOSAL_KEY)
+}
+
+#[deprecated(since = "0.5.0")]
+#[allow(deprecated)]
+pub fn legacy_join_proposals_read(storage: &dyn Storage) -> ReadonlyBucket<JoinProposal> {
+    bucket_read(storage, JOIN_PROPOSAL_KEY)
+}
+
+/// Remove legacy join proposals. Members are no longer admitted by vote, so open proposals
+/// have nothing to migrate to.
+#[allow(deprecated)]
+pub fn migrate_join_proposals(
+    deps: DepsMut,
+    _current_version: Version,
+    _msg: &MigrateMsg,
+) -> Result<(), ContractError> {
+    let keys = legacy_join_proposals_read(deps.storage)
+        .range(None, None, Order::Ascending)
+        .map(|item| item.map(|(key, _)| key))
+        .collect::<StdResult<Vec<Vec<u8>>>>()?;
+    for key in keys {
+        legacy_join_proposals(deps.storage).remove(&key);
+    }
+    Ok(())
+}
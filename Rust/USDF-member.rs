@@ -0,0 +1,173 @@
+use cosmwasm_std::{Addr, DepsMut, Order, StdResult, Storage, Timestamp, Uint128};
+use cosmwasm_storage::{bucket, bucket_read, Bucket, ReadonlyBucket};
+use schemars::JsonSchema;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+
+use crate::error::ContractError;
+use crate::msg::MigrateMsg;
+
+pub static MEMBER_KEY: &[u8] = b"member";
+pub static MEMBER_KEY_V2: &[u8] = b"memberv2";
+pub static KYC_VERIFICATION_KEY: &[u8] = b"kyc_verification";
+pub static MEMBER_TIER_KEY: &[u8] = b"member_tier";
+pub static RESERVE_ATTESTATION_KEY: &[u8] = b"reserve_attestation";
+pub static SUPPLY_TOTALS_KEY: &[u8] = b"supply_totals";
+pub static WIND_DOWN_KEY: &[u8] = b"wind_down";
+
+/// A member admitted by vote, with its own backing denom.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[deprecated(since = "0.5.0")]
+pub struct Member {
+    pub id: Addr,
+    pub supply: Uint128,
+    pub max_supply: Uint128,
+    pub denom: String,
+    pub joined: Uint128,
+    pub weight: Uint128,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MemberV2 {
+    pub id: Addr,
+    // The outstanding token supply minted by the member.
+    pub supply: Uint128,
+    // The attested reserves backing the member supply.
+    pub max_supply: Uint128,
+    // The block height the member joined at.
+    pub joined: Uint128,
+    pub name: String,
+    // Attributes identifying the customers of the member.
+    pub kyc_attrs: Vec<String>,
+}
+
+#[deprecated(since = "0.5.0")]
+#[allow(deprecated)]
+pub fn legacy_members(storage: &mut dyn Storage) -> Bucket<Member> {
+    bucket(storage, MEMBER_KEY)
+}
+
+#[deprecated(since = "0.5.0")]
+#[allow(deprecated)]
+pub fn legacy_members_read(storage: &dyn Storage) -> ReadonlyBucket<Member> {
+    bucket_read(storage, MEMBER_KEY)
+}
+
+pub fn members(storage: &mut dyn Storage) -> Bucket<MemberV2> {
+    bucket(storage, MEMBER_KEY_V2)
+}
+
+pub fn members_read(storage: &dyn Storage) -> ReadonlyBucket<MemberV2> {
+    bucket_read(storage, MEMBER_KEY_V2)
+}
+
+/// Move legacy members to the current format. Kyc attributes are added per member after
+/// the migration.
+#[allow(deprecated)]
+pub fn migrate_members(
+    deps: DepsMut,
+    _current_version: Version,
+    _msg: &MigrateMsg,
+) -> Result<(), ContractError> {
+    let legacy = legacy_members_read(deps.storage)
+        .range(None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(Vec<u8>, Member)>>>()?;
+    for (key, member) in legacy {
+        members(deps.storage).save(
+            &key,
+            &MemberV2 {
+                id: member.id,
+                supply: member.supply,
+                max_supply: member.max_supply,
+                joined: member.joined,
+                name: member.name,
+                kyc_attrs: vec![],
+            },
+        )?;
+        legacy_members(deps.storage).remove(&key);
+    }
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct KycVerification {
+    pub address: Addr,
+    // The member that verified the customer.
+    pub member: Addr,
+    // Block times the verification was made and lapses at.
+    pub verified: u64,
+    pub expires: u64,
+}
+
+// Customer kyc verifications keyed by customer address.
+pub fn kyc_verifications(storage: &mut dyn Storage) -> Bucket<KycVerification> {
+    bucket(storage, KYC_VERIFICATION_KEY)
+}
+
+pub fn kyc_verifications_read(storage: &dyn Storage) -> ReadonlyBucket<KycVerification> {
+    bucket_read(storage, KYC_VERIFICATION_KEY)
+}
+
+// Velocity tier names keyed by member address.
+pub fn member_tiers(storage: &mut dyn Storage) -> Bucket<String> {
+    bucket(storage, MEMBER_TIER_KEY)
+}
+
+pub fn member_tiers_read(storage: &dyn Storage) -> ReadonlyBucket<String> {
+    bucket_read(storage, MEMBER_TIER_KEY)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReserveAttestation {
+    pub member: Addr,
+    pub reserves: Uint128,
+    pub attestor: String,
+    // The report the attestation refers to.
+    pub reference: String,
+    pub height: Uint128,
+    pub time: Timestamp,
+}
+
+// The latest reserve attestation keyed by member address.
+pub fn reserve_attestations(storage: &mut dyn Storage) -> Bucket<ReserveAttestation> {
+    bucket(storage, RESERVE_ATTESTATION_KEY)
+}
+
+pub fn reserve_attestations_read(storage: &dyn Storage) -> ReadonlyBucket<ReserveAttestation> {
+    bucket_read(storage, RESERVE_ATTESTATION_KEY)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SupplyTotals {
+    // Lifetime amounts minted and burned by a member.
+    pub minted: Uint128,
+    pub burned: Uint128,
+}
+
+// Supply totals keyed by member address. Totals are kept after a member is removed.
+pub fn supply_totals(storage: &mut dyn Storage) -> Bucket<SupplyTotals> {
+    bucket(storage, SUPPLY_TOTALS_KEY)
+}
+
+pub fn supply_totals_read(storage: &dyn Storage) -> ReadonlyBucket<SupplyTotals> {
+    bucket_read(storage, SUPPLY_TOTALS_KEY)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WindDown {
+    pub id: Addr,
+    // The block height the wind-down started at.
+    pub started: Uint128,
+    // The member supply when the wind-down started.
+    pub starting_supply: Uint128,
+}
+
+// Members winding down keyed by member address.
+pub fn wind_downs(storage: &mut dyn Storage) -> Bucket<WindDown> {
+    bucket(storage, WIND_DOWN_KEY)
+}
+
+pub fn wind_downs_read(storage: &dyn Storage) -> ReadonlyBucket<WindDown> {
+    bucket_read(storage, WIND_DOWN_KEY)
+}
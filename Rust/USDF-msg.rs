@@ -0,0 +1,448 @@
+use cosmwasm_std::{Addr, Binary, IbcChannel, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::audit::AuditEntry;
+use crate::member::{KycVerification, MemberV2, ReserveAttestation};
+use crate::state::{ExecutorAllowance, FrozenAccount, Role, SchemaVersion};
+use crate::supply_proposal::SupplyProposal;
+
+/// A message sent to initialize the contract state.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InitMsg {
+    pub denom: String,
+    // Marker creation params, defaulting to a restricted marker without extra grants.
+    pub marker: Option<MarkerParams>,
+}
+
+/// Params for creating the token marker.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct MarkerParams {
+    pub allow_forced_transfer: bool,
+    pub required_attributes: Vec<String>,
+    // Marker access granted to accounts other than the contract and its admin.
+    pub access_grants: Vec<MarkerGrant>,
+    pub metadata: Option<DenomMetadata>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MarkerGrant {
+    pub address: String,
+    // Marker permission names, eg "transfer".
+    pub permissions: Vec<String>,
+}
+
+/// Display metadata published for the token denom.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DenomMetadata {
+    pub description: String,
+    pub display: String,
+    pub name: String,
+    pub symbol: String,
+    // The exponent of the display denom.
+    pub exponent: u32,
+}
+
+/// A vote on a join proposal, from before members were admitted by the admin.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+#[deprecated(since = "0.5.0")]
+pub enum VoteChoice {
+    Yes,
+    No,
+}
+
+/// A message sent to transfer funds and/or manage the consortium.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    Join {
+        id: String,
+        name: String,
+        kyc_attrs: Vec<String>,
+    },
+    Remove {
+        id: String,
+    },
+    Transfer {
+        amount: Uint128,
+        recipient: String,
+    },
+    BatchTransfer {
+        transfers: Vec<(String, Uint128)>,
+    },
+    Mint {
+        amount: Uint128,
+        address: Option<String>,
+    },
+    Burn {
+        amount: Uint128,
+    },
+    RequestMint {
+        amount: Uint128,
+        reference: String,
+    },
+    RequestRedeem {
+        amount: Uint128,
+        reference: String,
+    },
+    ConfirmRequest {
+        reference: String,
+    },
+    RejectRequest {
+        reference: String,
+        reason: String,
+    },
+    ExpireRequest {
+        reference: String,
+    },
+    SnapshotHolders {
+        holders: Vec<String>,
+    },
+    DistributeYield {
+        snapshot_id: u64,
+        amount: Uint128,
+    },
+    AddKyc {
+        id: Option<String>,
+        kyc_attr: String,
+    },
+    RemoveKyc {
+        id: Option<String>,
+        kyc_attr: String,
+    },
+    VerifyKyc {
+        id: String,
+        expires: u64,
+    },
+    SetKycEnforced {
+        enforced: bool,
+    },
+    SetAdmin {
+        id: String,
+    },
+    AcceptAdmin {},
+    GrantRole {
+        role: Role,
+        id: String,
+    },
+    RevokeRole {
+        role: Role,
+        id: String,
+    },
+    AuthorizeUpgrade {
+        version: String,
+    },
+    AddExecutor {
+        id: String,
+    },
+    RemoveExecutor {
+        id: String,
+    },
+    ExecutorTransfer {
+        amount: Uint128,
+        sender: String,
+        recipient: String,
+    },
+    ExecutorBatchTransfer {
+        sender: String,
+        transfers: Vec<(String, Uint128)>,
+    },
+    SetExecutorAllowance {
+        id: String,
+        max_per_tx: Option<Uint128>,
+        max_per_period: Option<Uint128>,
+        period_seconds: u64,
+        allowed_sources: Vec<String>,
+        allowed_members: Vec<String>,
+        expires: Option<u64>,
+    },
+    SetApprovers {
+        approvers: Vec<String>,
+        quorum: u32,
+    },
+    SetApprovalThreshold {
+        id: String,
+        threshold: Option<Uint128>,
+    },
+    ApproveProposal {
+        proposal_id: u64,
+    },
+    ExecuteProposal {
+        proposal_id: u64,
+    },
+    CancelProposal {
+        proposal_id: u64,
+    },
+    AttestReserves {
+        id: String,
+        reserves: Uint128,
+        attestor: String,
+        reference: String,
+    },
+    Freeze {
+        id: String,
+        reason: String,
+    },
+    Unfreeze {
+        id: String,
+    },
+    SetRecoveryAccount {
+        id: String,
+    },
+    Seize {
+        id: String,
+        amount: Option<Uint128>,
+    },
+    SetPaused {
+        mint: Option<bool>,
+        burn: Option<bool>,
+        transfer: Option<bool>,
+        executor_transfer: Option<bool>,
+    },
+    SetTransferFee {
+        rate_bps: u64,
+        collector: Option<String>,
+    },
+    SetVelocityTier {
+        tier: String,
+        address_limit: Option<Uint128>,
+        member_limit: Option<Uint128>,
+        window_seconds: u64,
+    },
+    SetMemberTier {
+        id: String,
+        tier: Option<String>,
+    },
+    SetIbcRoute {
+        channel_id: String,
+        receivers: Vec<String>,
+    },
+    IbcTransfer {
+        channel_id: String,
+        receiver: String,
+        amount: Uint128,
+        timeout_seconds: Option<u64>,
+    },
+}
+
+/// A message sent to query contract config state.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    GetMembers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+        kyc_attr: Option<String>,
+        min_joined: Option<Uint128>,
+        max_joined: Option<Uint128>,
+    },
+    GetMemberByAddress {
+        address: String,
+    },
+    GetMember {
+        id: String,
+    },
+    GetContractInfo {},
+    GetVersionInfo {},
+    GetProposals {},
+    GetProposal {
+        proposal_id: u64,
+    },
+    GetReserves {
+        id: String,
+    },
+    GetFrozenAccounts {},
+    GetPauseState {},
+    GetRoles {
+        id: String,
+    },
+    GetPendingAdmin {},
+    GetWindDown {
+        id: String,
+    },
+    GetTransferFee {
+        sender: String,
+        recipient: String,
+        amount: Uint128,
+    },
+    GetExecutorAllowance {
+        id: String,
+    },
+    GetRequest {
+        reference: String,
+    },
+    GetExpiringKyc {
+        within_seconds: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    GetSnapshot {
+        snapshot_id: u64,
+    },
+    GetDistribution {
+        distribution_id: u64,
+    },
+    GetSupplyReconciliation {},
+    GetIbcChannel {
+        channel_id: String,
+    },
+    GetSchemaVersions {},
+    GetAuditLog {
+        member: Option<String>,
+        action: Option<String>,
+        start_height: Option<u64>,
+        end_height: Option<u64>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+/// A message sent when migrating to a new code ID.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {
+    // Updated marker params, whose access grants are reconciled with the marker.
+    pub marker: Option<MarkerParams>,
+    // Fail with the migration plan instead of migrating.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Members {
+    pub members: Vec<MemberV2>,
+    // The last member scanned, to page on from while more members remain.
+    pub next_start_after: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Proposals {
+    pub proposals: Vec<SupplyProposal>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MemberReserves {
+    pub id: Addr,
+    pub supply: Uint128,
+    pub reserves: Uint128,
+    // Reserves not yet backing supply.
+    pub available: Uint128,
+    pub attestation: Option<ReserveAttestation>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FrozenAccounts {
+    pub accounts: Vec<FrozenAccount>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Roles {
+    pub id: Addr,
+    pub roles: Vec<Role>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WindDownStatus {
+    pub id: Addr,
+    pub exiting: bool,
+    pub started: Option<Uint128>,
+    pub starting_supply: Uint128,
+    pub supply: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TransferFee {
+    pub amount: Uint128,
+    pub fee: Uint128,
+    // The amount the recipient receives.
+    pub net_amount: Uint128,
+    pub fee_collector: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ExecutorAllowanceStatus {
+    pub id: Addr,
+    pub allowance: ExecutorAllowance,
+    pub expired: bool,
+    // What remains of the period limit in the current period.
+    pub period_available: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct KycVerifications {
+    pub verifications: Vec<KycVerification>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MemberSupply {
+    pub id: Addr,
+    pub minted: Uint128,
+    pub burned: Uint128,
+    // Whether the member has since been removed.
+    pub removed: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SupplyReconciliation {
+    pub denom: String,
+    pub marker_supply: Uint128,
+    // Token held by the marker account for burn or redemption.
+    pub escrowed: Uint128,
+    pub minted: Uint128,
+    pub burned: Uint128,
+    pub outstanding: Uint128,
+    // Marker supply above or below the outstanding member supply.
+    pub excess: Uint128,
+    pub shortfall: Uint128,
+    pub members: Vec<MemberSupply>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AuditLog {
+    pub entries: Vec<AuditEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SchemaVersions {
+    pub versions: Vec<SchemaVersion>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrationStep {
+    pub record_type: String,
+    pub from_version: String,
+    pub to_version: String,
+    // The number of legacy records the step migrates.
+    pub records: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrationPlan {
+    pub from_version: String,
+    pub to_version: String,
+    pub steps: Vec<MigrationStep>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct IbcChannelStatus {
+    pub channel: IbcChannel,
+    pub receivers: Vec<String>,
+    pub escrowed: Uint128,
+}
+
+/// The ICS-20 fungible token packet data.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Ics20Packet {
+    pub amount: Uint128,
+    pub denom: String,
+    pub receiver: String,
+    pub sender: String,
+}
+
+/// The ICS-20 acknowledgement.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Ics20Ack {
+    Result(Binary),
+    Error(String),
+}
@@ -0,0 +1,60 @@
+use std::fmt;
+
+use cosmwasm_std::{Addr, Storage, Uint128};
+use cosmwasm_storage::{bucket, bucket_read, Bucket, ReadonlyBucket};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+pub static SETTLEMENT_REQUEST_KEY: &[u8] = b"settlement_request";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RequestKind {
+    Mint,
+    Redeem,
+}
+
+impl fmt::Display for RequestKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            RequestKind::Mint => "mint",
+            RequestKind::Redeem => "redeem",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RequestStatus {
+    Pending,
+    Confirmed,
+    Rejected,
+    Expired,
+}
+
+/// A customer request to mint against, or redeem for, a fiat wire settled by its member.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SettlementRequest {
+    // The wire reference, unique across all requests.
+    pub reference: String,
+    pub kind: RequestKind,
+    pub requester: Addr,
+    pub member: Addr,
+    pub amount: Uint128,
+    pub status: RequestStatus,
+    // Block heights the request was filed at and expires at.
+    pub created: u64,
+    pub expires: u64,
+    // Why the member rejected the request.
+    pub reason: Option<String>,
+}
+
+// Requests keyed by wire reference.
+pub fn settlement_requests(storage: &mut dyn Storage) -> Bucket<SettlementRequest> {
+    bucket(storage, SETTLEMENT_REQUEST_KEY)
+}
+
+pub fn settlement_requests_read(storage: &dyn Storage) -> ReadonlyBucket<SettlementRequest> {
+    bucket_read(storage, SETTLEMENT_REQUEST_KEY)
+}
@@ -0,0 +1,367 @@
+use std::fmt;
+
+use cosmwasm_std::{Addr, Decimal, DepsMut, IbcChannel, Storage, Uint128};
+use cosmwasm_storage::{
+    bucket, bucket_read, singleton, singleton_read, Bucket, ReadonlyBucket, ReadonlySingleton,
+    Singleton,
+};
+use schemars::JsonSchema;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+
+use crate::error::ContractError;
+use crate::msg::{MarkerParams, MigrateMsg};
+
+pub static CONFIG_KEY: &[u8] = b"config";
+pub static CONFIG_KEY_V2: &[u8] = b"configv2";
+pub static APPROVAL_CONFIG_KEY: &[u8] = b"approval_config";
+pub static APPROVAL_THRESHOLD_KEY: &[u8] = b"approval_threshold";
+pub static EXECUTOR_ALLOWANCE_KEY: &[u8] = b"executor_allowance";
+pub static FEE_CONFIG_KEY: &[u8] = b"fee_config";
+pub static FROZEN_ACCOUNT_KEY: &[u8] = b"frozen_account";
+pub static IBC_CHANNEL_KEY: &[u8] = b"ibc_channel";
+pub static IBC_ESCROW_KEY: &[u8] = b"ibc_escrow";
+pub static IBC_ROUTE_KEY: &[u8] = b"ibc_route";
+pub static KYC_ENFORCED_KEY: &[u8] = b"kyc_enforced";
+pub static MARKER_PARAMS_KEY: &[u8] = b"marker_params";
+pub static PAUSE_STATE_KEY: &[u8] = b"pause_state";
+pub static PENDING_ADMIN_KEY: &[u8] = b"pending_admin";
+pub static RECOVERY_ACCOUNT_KEY: &[u8] = b"recovery_account";
+pub static ROLE_KEY: &[u8] = b"role";
+pub static SCHEMA_VERSION_KEY: &[u8] = b"schema_version";
+pub static UPGRADE_AUTHORIZATION_KEY: &[u8] = b"upgrade_authorization";
+
+/// Contract state before members were admitted without a vote.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[deprecated(since = "0.5.0")]
+pub struct State {
+    // The contract admin.
+    pub admin: Addr,
+    // The percentage of member weight required to pass a join proposal.
+    pub quorum_pct: Decimal,
+    // The token denom.
+    pub dcc_denom: String,
+    // The number of blocks a join proposal was open for voting.
+    pub vote_duration: Uint128,
+    // Attributes required for holding the token.
+    pub kyc_attrs: Vec<String>,
+    // The voting weight of the admin.
+    pub admin_weight: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StateV2 {
+    // The contract admin.
+    pub admin: Addr,
+    // The token denom.
+    pub denom: String,
+    // Accounts allowed to transfer on behalf of token holders.
+    pub executors: Vec<String>,
+}
+
+#[deprecated(since = "0.5.0")]
+#[allow(deprecated)]
+pub fn legacy_config(storage: &mut dyn Storage) -> Singleton<State> {
+    singleton(storage, CONFIG_KEY)
+}
+
+#[deprecated(since = "0.5.0")]
+#[allow(deprecated)]
+pub fn legacy_config_read(storage: &dyn Storage) -> ReadonlySingleton<State> {
+    singleton_read(storage, CONFIG_KEY)
+}
+
+pub fn config(storage: &mut dyn Storage) -> Singleton<StateV2> {
+    singleton(storage, CONFIG_KEY_V2)
+}
+
+pub fn config_read(storage: &dyn Storage) -> ReadonlySingleton<StateV2> {
+    singleton_read(storage, CONFIG_KEY_V2)
+}
+
+/// Move legacy state to the current format, dropping the voting configuration.
+#[allow(deprecated)]
+pub fn migrate_state(
+    deps: DepsMut,
+    _current_version: Version,
+    _msg: &MigrateMsg,
+) -> Result<(), ContractError> {
+    if let Some(legacy) = legacy_config_read(deps.storage).may_load()? {
+        config(deps.storage).save(&StateV2 {
+            admin: legacy.admin,
+            denom: legacy.dcc_denom,
+            executors: vec![],
+        })?;
+        legacy_config(deps.storage).remove();
+    }
+    Ok(())
+}
+
+/// Give executors added before allowances existed an unlimited allowance, so their transfers
+/// keep working until an allowance is set for them.
+pub fn migrate_executor_allowances(
+    deps: DepsMut,
+    _current_version: Version,
+    _msg: &MigrateMsg,
+) -> Result<(), ContractError> {
+    let state = match config_read(deps.storage).may_load()? {
+        Some(state) => state,
+        None => return Ok(()),
+    };
+    for executor in state.executors {
+        let key = executor.as_bytes();
+        if executor_allowances_read(deps.storage)
+            .may_load(key)?
+            .is_none()
+        {
+            executor_allowances(deps.storage).save(
+                key,
+                &ExecutorAllowance {
+                    executor: Addr::unchecked(&executor),
+                    max_per_tx: None,
+                    max_per_period: None,
+                    period_seconds: 0,
+                    allowed_sources: vec![],
+                    allowed_members: vec![],
+                    expires: None,
+                    period_start: 0,
+                    period_spent: Uint128::zero(),
+                },
+            )?;
+        }
+    }
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ApprovalConfig {
+    // The accounts that may approve large mints and burns.
+    pub approvers: Vec<Addr>,
+    // The number of approvals a proposal needs before it can be executed.
+    pub quorum: u32,
+}
+
+pub fn approval_config(storage: &mut dyn Storage) -> Singleton<ApprovalConfig> {
+    singleton(storage, APPROVAL_CONFIG_KEY)
+}
+
+pub fn approval_config_read(storage: &dyn Storage) -> ReadonlySingleton<ApprovalConfig> {
+    singleton_read(storage, APPROVAL_CONFIG_KEY)
+}
+
+// Mint and burn amounts above which a member needs approval, keyed by member address.
+pub fn approval_thresholds(storage: &mut dyn Storage) -> Bucket<Uint128> {
+    bucket(storage, APPROVAL_THRESHOLD_KEY)
+}
+
+pub fn approval_thresholds_read(storage: &dyn Storage) -> ReadonlyBucket<Uint128> {
+    bucket_read(storage, APPROVAL_THRESHOLD_KEY)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ExecutorAllowance {
+    pub executor: Addr,
+    // The largest amount of a single transfer.
+    pub max_per_tx: Option<Uint128>,
+    // The total amount that may be transferred in a period.
+    pub max_per_period: Option<Uint128>,
+    pub period_seconds: u64,
+    // Accounts the executor may transfer from, directly or as customers of a member.
+    pub allowed_sources: Vec<Addr>,
+    pub allowed_members: Vec<Addr>,
+    // The block height the allowance expires at.
+    pub expires: Option<u64>,
+    // The block time the current period started, and the amount spent in it.
+    pub period_start: u64,
+    pub period_spent: Uint128,
+}
+
+pub fn executor_allowances(storage: &mut dyn Storage) -> Bucket<ExecutorAllowance> {
+    bucket(storage, EXECUTOR_ALLOWANCE_KEY)
+}
+
+pub fn executor_allowances_read(storage: &dyn Storage) -> ReadonlyBucket<ExecutorAllowance> {
+    bucket_read(storage, EXECUTOR_ALLOWANCE_KEY)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeConfig {
+    // The fee charged on transfers between members, in basis points.
+    pub rate_bps: u64,
+    // The account fees are paid to.
+    pub collector: Addr,
+}
+
+pub fn fee_config(storage: &mut dyn Storage) -> Singleton<FeeConfig> {
+    singleton(storage, FEE_CONFIG_KEY)
+}
+
+pub fn fee_config_read(storage: &dyn Storage) -> ReadonlySingleton<FeeConfig> {
+    singleton_read(storage, FEE_CONFIG_KEY)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FrozenAccount {
+    pub address: Addr,
+    pub reason: String,
+    // The block height the account was frozen at.
+    pub frozen: Uint128,
+}
+
+pub fn frozen_accounts(storage: &mut dyn Storage) -> Bucket<FrozenAccount> {
+    bucket(storage, FROZEN_ACCOUNT_KEY)
+}
+
+pub fn frozen_accounts_read(storage: &dyn Storage) -> ReadonlyBucket<FrozenAccount> {
+    bucket_read(storage, FROZEN_ACCOUNT_KEY)
+}
+
+// Connected ICS-20 channels, keyed by channel id.
+pub fn ibc_channels(storage: &mut dyn Storage) -> Bucket<IbcChannel> {
+    bucket(storage, IBC_CHANNEL_KEY)
+}
+
+pub fn ibc_channels_read(storage: &dyn Storage) -> ReadonlyBucket<IbcChannel> {
+    bucket_read(storage, IBC_CHANNEL_KEY)
+}
+
+// Token escrowed against each channel, keyed by channel id.
+pub fn ibc_escrows(storage: &mut dyn Storage) -> Bucket<Uint128> {
+    bucket(storage, IBC_ESCROW_KEY)
+}
+
+pub fn ibc_escrows_read(storage: &dyn Storage) -> ReadonlyBucket<Uint128> {
+    bucket_read(storage, IBC_ESCROW_KEY)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct IbcRoute {
+    pub channel_id: String,
+    // Counterparty addresses token may be sent to over the channel.
+    pub receivers: Vec<String>,
+}
+
+pub fn ibc_routes(storage: &mut dyn Storage) -> Bucket<IbcRoute> {
+    bucket(storage, IBC_ROUTE_KEY)
+}
+
+pub fn ibc_routes_read(storage: &dyn Storage) -> ReadonlyBucket<IbcRoute> {
+    bucket_read(storage, IBC_ROUTE_KEY)
+}
+
+pub fn kyc_enforced(storage: &mut dyn Storage) -> Singleton<bool> {
+    singleton(storage, KYC_ENFORCED_KEY)
+}
+
+pub fn kyc_enforced_read(storage: &dyn Storage) -> ReadonlySingleton<bool> {
+    singleton_read(storage, KYC_ENFORCED_KEY)
+}
+
+pub fn marker_params(storage: &mut dyn Storage) -> Singleton<MarkerParams> {
+    singleton(storage, MARKER_PARAMS_KEY)
+}
+
+pub fn marker_params_read(storage: &dyn Storage) -> ReadonlySingleton<MarkerParams> {
+    singleton_read(storage, MARKER_PARAMS_KEY)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct PauseState {
+    pub mint: bool,
+    pub burn: bool,
+    pub transfer: bool,
+    pub executor_transfer: bool,
+}
+
+pub fn pause_state(storage: &mut dyn Storage) -> Singleton<PauseState> {
+    singleton(storage, PAUSE_STATE_KEY)
+}
+
+pub fn pause_state_read(storage: &dyn Storage) -> ReadonlySingleton<PauseState> {
+    singleton_read(storage, PAUSE_STATE_KEY)
+}
+
+pub fn pending_admin(storage: &mut dyn Storage) -> Singleton<Addr> {
+    singleton(storage, PENDING_ADMIN_KEY)
+}
+
+pub fn pending_admin_read(storage: &dyn Storage) -> ReadonlySingleton<Addr> {
+    singleton_read(storage, PENDING_ADMIN_KEY)
+}
+
+pub fn recovery_account(storage: &mut dyn Storage) -> Singleton<Addr> {
+    singleton(storage, RECOVERY_ACCOUNT_KEY)
+}
+
+pub fn recovery_account_read(storage: &dyn Storage) -> ReadonlySingleton<Addr> {
+    singleton_read(storage, RECOVERY_ACCOUNT_KEY)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    MemberManager,
+    KycManager,
+    ExecutorManager,
+    Pauser,
+    Upgrader,
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Role::MemberManager => "member_manager",
+            Role::KycManager => "kyc_manager",
+            Role::ExecutorManager => "executor_manager",
+            Role::Pauser => "pauser",
+            Role::Upgrader => "upgrader",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// Roles granted to an account, keyed by account address.
+pub fn roles(storage: &mut dyn Storage) -> Bucket<Vec<Role>> {
+    bucket(storage, ROLE_KEY)
+}
+
+pub fn roles_read(storage: &dyn Storage) -> ReadonlyBucket<Vec<Role>> {
+    bucket_read(storage, ROLE_KEY)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SchemaVersion {
+    pub record_type: String,
+    // The contract version the records were last written by.
+    pub version: String,
+    // The version the records were migrated from, if they were migrated.
+    pub prior_version: Option<String>,
+    pub height: u64,
+}
+
+// Schema versions keyed by record type.
+pub fn schema_versions(storage: &mut dyn Storage) -> Bucket<SchemaVersion> {
+    bucket(storage, SCHEMA_VERSION_KEY)
+}
+
+pub fn schema_versions_read(storage: &dyn Storage) -> ReadonlyBucket<SchemaVersion> {
+    bucket_read(storage, SCHEMA_VERSION_KEY)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UpgradeAuthorization {
+    // The contract version a migration may upgrade to.
+    pub version: String,
+    pub authorized_by: Addr,
+    pub height: u64,
+}
+
+pub fn upgrade_authorization(storage: &mut dyn Storage) -> Singleton<UpgradeAuthorization> {
+    singleton(storage, UPGRADE_AUTHORIZATION_KEY)
+}
+
+pub fn upgrade_authorization_read(
+    storage: &dyn Storage,
+) -> ReadonlySingleton<UpgradeAuthorization> {
+    singleton_read(storage, UPGRADE_AUTHORIZATION_KEY)
+}
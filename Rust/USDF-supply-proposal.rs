@@ -0,0 +1,50 @@
+use cosmwasm_std::{Addr, Storage, Uint128};
+use cosmwasm_storage::{
+    bucket, bucket_read, singleton, singleton_read, Bucket, ReadonlyBucket, ReadonlySingleton,
+    Singleton,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+pub static PROPOSAL_SEQ_KEY: &[u8] = b"supply_proposal_seq";
+pub static SUPPLY_PROPOSAL_KEY: &[u8] = b"supply_proposal";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SupplyAction {
+    Mint,
+    Burn,
+    // Mint yield and pay it out to the holders of a snapshot.
+    Distribute { snapshot_id: u64 },
+}
+
+/// A mint, burn or yield distribution above the member approval threshold, held until approvers reach quorum.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SupplyProposal {
+    pub id: u64,
+    pub member: Addr,
+    pub action: SupplyAction,
+    pub amount: Uint128,
+    // The account minted token is withdrawn to.
+    pub address: Option<Addr>,
+    pub approvals: Vec<Addr>,
+    // The block height the proposal was created at.
+    pub created: Uint128,
+}
+
+pub fn proposal_seq(storage: &mut dyn Storage) -> Singleton<u64> {
+    singleton(storage, PROPOSAL_SEQ_KEY)
+}
+
+pub fn proposal_seq_read(storage: &dyn Storage) -> ReadonlySingleton<u64> {
+    singleton_read(storage, PROPOSAL_SEQ_KEY)
+}
+
+// Pending proposals keyed by big-endian proposal id.
+pub fn supply_proposals(storage: &mut dyn Storage) -> Bucket<SupplyProposal> {
+    bucket(storage, SUPPLY_PROPOSAL_KEY)
+}
+
+pub fn supply_proposals_read(storage: &dyn Storage) -> ReadonlyBucket<SupplyProposal> {
+    bucket_read(storage, SUPPLY_PROPOSAL_KEY)
+}
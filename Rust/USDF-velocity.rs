@@ -0,0 +1,64 @@
+use cosmwasm_std::{Storage, Uint128};
+use cosmwasm_storage::{bucket, bucket_read, Bucket, ReadonlyBucket};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+pub static ADDRESS_VELOCITY_KEY: &[u8] = b"address_velocity";
+pub static MEMBER_VELOCITY_KEY: &[u8] = b"member_velocity";
+pub static VELOCITY_TIER_KEY: &[u8] = b"velocity_tier";
+
+// The number of buckets a velocity window is divided into.
+pub const VELOCITY_BUCKETS: u64 = 24;
+
+/// Transfer limits over a rolling window, assigned to members by name.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VelocityTier {
+    pub name: String,
+    // The most a single account may send within the window.
+    pub address_limit: Option<Uint128>,
+    // The most all of a member's customers may send within the window.
+    pub member_limit: Option<Uint128>,
+    pub window_seconds: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VelocityBucket {
+    // The block time of the transfers divided by the bucket length.
+    pub index: u64,
+    pub amount: Uint128,
+}
+
+/// Transfers over a rolling window, summed into fixed time buckets.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct VelocityWindow {
+    // The bucket length the window was recorded with.
+    pub bucket_seconds: u64,
+    // At most VELOCITY_BUCKETS buckets, oldest first.
+    pub buckets: Vec<VelocityBucket>,
+}
+
+pub fn velocity_tiers(storage: &mut dyn Storage) -> Bucket<VelocityTier> {
+    bucket(storage, VELOCITY_TIER_KEY)
+}
+
+pub fn velocity_tiers_read(storage: &dyn Storage) -> ReadonlyBucket<VelocityTier> {
+    bucket_read(storage, VELOCITY_TIER_KEY)
+}
+
+// Windows keyed by sending account address.
+pub fn address_velocity(storage: &mut dyn Storage) -> Bucket<VelocityWindow> {
+    bucket(storage, ADDRESS_VELOCITY_KEY)
+}
+
+pub fn address_velocity_read(storage: &dyn Storage) -> ReadonlyBucket<VelocityWindow> {
+    bucket_read(storage, ADDRESS_VELOCITY_KEY)
+}
+
+// Windows keyed by member address.
+pub fn member_velocity(storage: &mut dyn Storage) -> Bucket<VelocityWindow> {
+    bucket(storage, MEMBER_VELOCITY_KEY)
+}
+
+pub fn member_velocity_read(storage: &dyn Storage) -> ReadonlyBucket<VelocityWindow> {
+    bucket_read(storage, MEMBER_VELOCITY_KEY)
+}
@@ -7,7 +7,15 @@ use crate::contract_info::{
 use crate::error::ContractError;
 use crate::error::ContractError::InvalidPricePrecisionSizePair;
 use crate::execute::modify_contract::modify_contract;
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, Validate};
+use crate::fee_schedule::{FeeSchedule, ACCOUNT_VOLUME, FEE_SCHEDULE};
+use crate::msg::{
+    BestBidAsk, BookDepth, ExecuteMsg, FeeRates, InstantiateMsg, MigrateMsg, OrderSide, PriceLevel,
+    QueryMsg, TimeInForce, Validate,
+};
+use crate::order_book::{
+    BookPosition, ASK_BOOK, ASK_EXPIRIES, ASK_OWNERS, ASK_POSITIONS, BID_BOOK, BID_EXPIRIES,
+    BID_OWNERS, BID_POSITIONS, ORDER_SEQUENCE,
+};
 use crate::util::{
     add_transfer, get_attributes, is_invalid_price_precision, is_restricted_marker,
     transfer_marker_coins,
@@ -17,15 +25,31 @@ use crate::version_info::{
     PACKAGE_VERSION,
 };
 use cosmwasm_std::{
-    attr, coin, coins, entry_point, to_binary, Addr, Binary, Coin, Deps, DepsMut, Env, MessageInfo,
-    Response, StdError, StdResult, Uint128,
+    attr, coin, coins, entry_point, to_binary, Addr, Binary, Coin, Deps, DepsMut, Empty, Env,
+    MessageInfo, Order, Response, StdError, StdResult, Storage, Uint128,
 };
+use cw_storage_plus::{Bound, Map};
 use provwasm_std::types::provenance::attribute::v1::AttributeQuerier;
 use rust_decimal::prelude::{FromPrimitive, FromStr, ToPrimitive, Zero};
 use rust_decimal::{Decimal, RoundingStrategy};
 use std::cmp::Ordering;
 use std::collections::HashSet;
 
+// maximum number of resting orders an incoming order scans in one transaction, skipped ones included
+pub const MAX_AUTO_MATCH_ITERATIONS: usize = 25;
+
+// query pagination limits
+pub const DEFAULT_QUERY_LIMIT: u32 = 10;
+pub const MAX_QUERY_LIMIT: u32 = 30;
+pub const MAX_BOOK_DEPTH_LEVELS: u32 = 50;
+
+// maximum number of expired orders refunded by one sweep
+pub const MAX_SWEEP_ORDERS: u32 = 30;
+
+// fee tiers are keyed to volume filled over this many days
+pub const VOLUME_WINDOW_DAYS: u64 = 30;
+pub const SECONDS_PER_DAY: u64 = 86_400;
+
 // smart contract initialization entrypoint
 #[entry_point]
 pub fn instantiate(
@@ -146,6 +170,8 @@ pub fn execute(
             quote,
             price,
             size,
+            auto_match,
+            time_in_force,
         } => create_ask(
             deps,
             env,
@@ -159,6 +185,8 @@ pub fn execute(
                 price,
                 size,
             },
+            auto_match.unwrap_or(false),
+            time_in_force.unwrap_or(TimeInForce::GoodTillCancel),
         ),
         ExecuteMsg::CreateBid {
             id,
@@ -168,6 +196,8 @@ pub fn execute(
             quote,
             quote_size,
             size,
+            auto_match,
+            time_in_force,
         } => create_bid(
             deps,
             env,
@@ -189,7 +219,11 @@ pub fn execute(
                     denom: quote,
                 },
             },
+            auto_match.unwrap_or(false),
+            time_in_force.unwrap_or(TimeInForce::GoodTillCancel),
         ),
+        ExecuteMsg::UpdateAsk { id, price, size } => update_ask(deps, env, info, id, price, size),
+        ExecuteMsg::UpdateBid { id, price, size } => update_bid(deps, env, info, id, price, size),
         ExecuteMsg::CancelAsk { id } => cancel_ask(deps, env, info, id),
         ExecuteMsg::CancelBid { id } => {
             reverse_bid(deps, env, info, id, ContractAction::CancelBid, None)
@@ -206,12 +240,16 @@ pub fn execute(
         ExecuteMsg::ExpireBid { id } => {
             reverse_bid(deps, env, info, id, ContractAction::ExpireBid, None)
         }
+        ExecuteMsg::SweepExpired { limit } => sweep_expired(deps, env, info, limit),
         ExecuteMsg::RejectAsk { id, size } => {
             reverse_ask(deps, env, info, id, ContractAction::RejectAsk, size)
         }
         ExecuteMsg::RejectBid { id, size } => {
             reverse_bid(deps, env, info, id, ContractAction::RejectBid, size)
         }
+        ExecuteMsg::SetFeeSchedule { fee_schedule } => {
+            set_fee_schedule(deps, env, info, fee_schedule)
+        }
         ExecuteMsg::ModifyContract {
             approvers,
             executors,
@@ -338,13 +376,17 @@ fn approve_ask(
 
 // create ask entrypoint
 fn create_ask(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: &MessageInfo,
     mut ask_order: AskOrderV1,
+    auto_match: bool,
+    time_in_force: TimeInForce,
 ) -> Result<Response, ContractError> {
     let contract_info = get_contract_info(deps.storage)?;
 
+    let expires = validate_time_in_force(&env, &time_in_force, auto_match)?;
+
     // error if order base is not contract base nor contract convertible base
     if ask_order.base.ne(&contract_info.base_denom)
         && !contract_info
@@ -425,6 +467,16 @@ fn create_ask(
     }
 
     if ask_order.base.ne(&contract_info.base_denom) {
+        // convertible asks can't match until approved, so can't be filled immediately
+        if matches!(
+            time_in_force,
+            TimeInForce::ImmediateOrCancel | TimeInForce::FillOrKill
+        ) {
+            return Err(ContractError::InvalidFields {
+                fields: vec![String::from("time_in_force")],
+            });
+        }
+
         ask_order.class = AskOrderClass::Convertible {
             status: AskOrderStatus::PendingIssuerApproval,
         };
@@ -440,6 +492,7 @@ fn create_ask(
     }
 
     ASKS_V1.save(deps.storage, ask_order.id.as_bytes(), &ask_order)?;
+    index_ask(deps.storage, &contract_info, &ask_order, expires)?;
 
     let mut response = Response::new().add_attributes(vec![
         attr("action", ContractAction::CreateAsk.to_string()),
@@ -457,23 +510,58 @@ fn create_ask(
             ask_order.size.into(),
             ask_order.base.to_owned(),
             env.contract.address.to_owned(),
-            ask_order.owner,
-            env.contract.address,
+            ask_order.owner.to_owned(),
+            env.contract.address.to_owned(),
         )?);
     }
 
+    // convertible asks must be approved before they can be matched
+    if auto_match && matches!(ask_order.class, AskOrderClass::Basic) {
+        let fills = match_incoming_ask(deps.branch(), env.to_owned(), &ask_order.id)?;
+        response = response
+            .add_submessages(fills.messages)
+            .add_attributes(fills.attributes);
+    }
+
+    // immediate orders don't rest on the book
+    if ASKS_V1
+        .may_load(deps.storage, ask_order.id.as_bytes())?
+        .is_some()
+    {
+        match time_in_force {
+            TimeInForce::ImmediateOrCancel => {
+                let refund = reverse_ask_order(
+                    deps.branch(),
+                    env,
+                    ask_order.id,
+                    ContractAction::ExpireAsk,
+                    None,
+                )?;
+                response = response
+                    .add_submessages(refund.messages)
+                    .add_attributes(refund.attributes);
+            }
+            TimeInForce::FillOrKill => return Err(ContractError::FillOrKillNotFilled),
+            _ => (),
+        }
+    }
+
     Ok(response)
 }
 
 // create bid entrypoint
 fn create_bid(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: &MessageInfo,
     mut bid_order: BidOrderV3,
+    auto_match: bool,
+    time_in_force: TimeInForce,
 ) -> Result<Response, ContractError> {
     let contract_info = get_contract_info(deps.storage)?;
 
+    let expires = validate_time_in_force(&env, &time_in_force, auto_match)?;
+
     let bid_price =
         Decimal::from_str(&bid_order.price).map_err(|_| ContractError::InvalidFields {
             fields: vec![String::from("price")],
@@ -514,15 +602,8 @@ fn create_bid(
         return Err(ContractError::SentFundsOrderMismatch);
     }
 
-    // Get the bid fee rate (0 if not set)
-    let bid_fee_rate = match contract_info.bid_fee_info {
-        Some(bid_fee_info) => {
-            Decimal::from_str(&bid_fee_info.rate).map_err(|_| ContractError::InvalidFields {
-                fields: vec![String::from("ContractInfo.bid_fee_info.rate")],
-            })?
-        }
-        None => Decimal::from(0),
-    };
+    // Get the bid fee rate (0 if not set), taken from the fee schedule when one is configured
+    let bid_fee_rate = bid_fee_escrow_rate(deps.as_ref(), &env, &contract_info, &bid_order.owner)?;
 
     // Calculate the expected fees (bid_fee_rate * total)
     let calculated_fee_size = bid_fee_rate
@@ -615,6 +696,7 @@ fn create_bid(
     }
 
     BIDS_V3.save(deps.storage, bid_order.id.as_bytes(), &bid_order)?;
+    index_bid(deps.storage, &contract_info, &bid_order, expires)?;
 
     let mut response = Response::new().add_attributes(vec![
         attr("action", ContractAction::CreateBid.to_string()),
@@ -635,817 +717,3452 @@ fn create_bid(
 
     if is_quote_restricted_marker {
         response = response.add_message(transfer_marker_coins(
-            match bid_order.fee {
+            match &bid_order.fee {
                 Some(fees) => (bid_order.quote.amount + fees.amount).into(),
                 _ => bid_order.quote.amount.into(),
             },
             bid_order.quote.denom.to_owned(),
             env.contract.address.to_owned(),
-            bid_order.owner,
-            env.contract.address,
+            bid_order.owner.to_owned(),
+            env.contract.address.to_owned(),
         )?);
     }
 
+    if auto_match {
+        let fills = match_incoming_bid(deps.branch(), env.to_owned(), &bid_order.id)?;
+        response = response
+            .add_submessages(fills.messages)
+            .add_attributes(fills.attributes);
+    }
+
+    // immediate orders don't rest on the book
+    if BIDS_V3
+        .may_load(deps.storage, bid_order.id.as_bytes())?
+        .is_some()
+    {
+        match time_in_force {
+            TimeInForce::ImmediateOrCancel => {
+                let refund = reverse_bid_order(
+                    deps.branch(),
+                    env,
+                    bid_order.id,
+                    ContractAction::ExpireBid,
+                    None,
+                )?;
+                response = response
+                    .add_submessages(refund.messages)
+                    .add_attributes(refund.attributes);
+            }
+            TimeInForce::FillOrKill => return Err(ContractError::FillOrKillNotFilled),
+            _ => (),
+        }
+    }
+
     Ok(response)
 }
 
-// cancel ask entrypoint
-fn cancel_ask(
-    deps: DepsMut,
+// get the expiry of a good till time order, and ensure immediate orders are matched on chain
+fn validate_time_in_force(
+    env: &Env,
+    time_in_force: &TimeInForce,
+    auto_match: bool,
+) -> Result<Option<u64>, ContractError> {
+    match time_in_force {
+        TimeInForce::GoodTillCancel => Ok(None),
+        TimeInForce::GoodTillTime { expires } => {
+            if expires.le(&env.block.time) {
+                return Err(ContractError::InvalidFields {
+                    fields: vec![String::from("time_in_force")],
+                });
+            }
+            Ok(Some(expires.seconds()))
+        }
+        TimeInForce::ImmediateOrCancel | TimeInForce::FillOrKill => {
+            if !auto_match {
+                return Err(ContractError::TimeInForceRequiresAutoMatch);
+            }
+            Ok(None)
+        }
+    }
+}
+
+// refund expired good till time orders, callable by anyone
+fn sweep_expired(
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    id: String,
+    limit: Option<u32>,
 ) -> Result<Response, ContractError> {
     // return error if funds sent
     if !info.funds.is_empty() {
-        return Err(ContractError::CancelWithFunds);
-    }
-
-    let AskOrderV1 {
-        id,
-        owner,
-        class,
-        base,
-        size,
-        ..
-    } = ASKS_V1
-        .load(deps.storage, id.as_bytes())
-        .map_err(|error| ContractError::LoadOrderFailed { error })?;
-    if !info.sender.eq(&owner) {
-        return Err(ContractError::Unauthorized);
+        return Err(ContractError::ExpireWithFunds);
     }
 
-    // remove the ask order from storage
-    ASKS_V1.remove(deps.storage, id.as_bytes());
-
-    // is ask base a marker
-    let is_base_restricted_marker = is_restricted_marker(&deps.querier, base.clone());
-
-    let mut response = Response::new();
-
-    // return 'base' to owner, return converted_base to issuer if applicable
-    response = add_transfer(
-        response,
-        is_base_restricted_marker,
-        size.into(),
-        base,
-        owner,
-        env.contract.address.to_owned(),
-        env.contract.address.to_owned(),
-    );
+    let limit = limit.unwrap_or(MAX_SWEEP_ORDERS).min(MAX_SWEEP_ORDERS) as usize;
+    let cutoff = env.block.time.seconds() + 1;
+
+    let expired_asks = ASK_EXPIRIES
+        .keys(
+            deps.storage,
+            None,
+            Some(Bound::exclusive((cutoff, ""))),
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| item.map(|(_, id)| id))
+        .collect::<StdResult<Vec<String>>>()?;
+
+    let expired_bids = BID_EXPIRIES
+        .keys(
+            deps.storage,
+            None,
+            Some(Bound::exclusive((cutoff, ""))),
+            Order::Ascending,
+        )
+        .take(limit - expired_asks.len())
+        .map(|item| item.map(|(_, id)| id))
+        .collect::<StdResult<Vec<String>>>()?;
 
-    response = response.add_attributes(vec![
-        attr("action", ContractAction::CancelAsk.to_string()),
-        attr("id", id),
+    let mut response = Response::new().add_attributes(vec![
+        attr("action", ContractAction::SweepExpired.to_string()),
+        attr("expired_asks", expired_asks.len().to_string()),
+        attr("expired_bids", expired_bids.len().to_string()),
     ]);
 
-    if let AskOrderClass::Convertible {
-        status: AskOrderStatus::Ready {
-            approver,
-            converted_base,
-        },
-    } = class
-    {
-        // is convertible a marker
-        let is_convertible_restricted_marker =
-            is_restricted_marker(&deps.querier, converted_base.denom.clone());
+    for id in expired_asks {
+        let refund = reverse_ask_order(
+            deps.branch(),
+            env.to_owned(),
+            id,
+            ContractAction::ExpireAsk,
+            None,
+        )?;
+        response = response
+            .add_submessages(refund.messages)
+            .add_attributes(refund.attributes);
+    }
 
-        response = add_transfer(
-            response,
-            is_convertible_restricted_marker,
-            converted_base.amount.into(),
-            converted_base.denom,
-            approver,
-            env.contract.address.to_owned(),
-            env.contract.address,
-        );
+    for id in expired_bids {
+        let refund = reverse_bid_order(
+            deps.branch(),
+            env.to_owned(),
+            id,
+            ContractAction::ExpireBid,
+            None,
+        )?;
+        response = response
+            .add_submessages(refund.messages)
+            .add_attributes(refund.attributes);
     }
 
     Ok(response)
 }
 
-// reverse ask entrypoint
-fn reverse_ask(
+// update ask entrypoint, amends the price or remaining size of a resting ask
+fn update_ask(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     id: String,
-    action: ContractAction,
-    cancel_size: Option<Uint128>,
+    price: Option<String>,
+    size: Option<Uint128>,
 ) -> Result<Response, ContractError> {
-    // return error if id is empty
-    if id.is_empty() {
-        return Err(ContractError::Unauthorized);
-    }
-
-    // return error if funds sent
-    if !info.funds.is_empty() {
-        return Err(ContractError::ExpireWithFunds);
+    if price.is_none() && size.is_none() {
+        return Err(ContractError::InvalidFields {
+            fields: vec![String::from("price"), String::from("size")],
+        });
     }
 
     let contract_info = get_contract_info(deps.storage)?;
 
-    if !contract_info.executors.contains(&info.sender) {
-        return Err(ContractError::Unauthorized);
-    }
-
-    // retrieve the order
     let mut ask_order = ASKS_V1
         .load(deps.storage, id.as_bytes())
         .map_err(|error| ContractError::LoadOrderFailed { error })?;
 
-    // determine the effective cancel size
-    let effective_cancel_size = match cancel_size {
-        None => ask_order.size,
-        Some(cancel_size) => cancel_size,
-    };
+    if !info.sender.eq(&ask_order.owner) {
+        return Err(ContractError::Unauthorized);
+    }
 
-    // error if cancel size is not multiple of size_increment
-    if (effective_cancel_size.u128() % contract_info.size_increment.u128()).ne(&0) {
+    if order_expired(deps.storage, &env, &ASK_POSITIONS, &id)? {
+        return Err(ContractError::OrderExpired { id });
+    }
+
+    let new_price = price.unwrap_or_else(|| ask_order.price.to_owned());
+    let new_size = size.unwrap_or(ask_order.size);
+
+    // convertible ask sizes are tied to the issuer's approval
+    if new_size.ne(&ask_order.size) && !matches!(ask_order.class, AskOrderClass::Basic) {
         return Err(ContractError::InvalidFields {
             fields: vec![String::from("size")],
         });
     }
 
-    // subtract the cancel size from the order size
-    ask_order.size = ask_order
-        .size
-        .checked_sub(effective_cancel_size)
-        .map_err(|_| ContractError::InvalidFields {
+    // error if new size is zero or not multiple of size_increment
+    if new_size.is_zero() || (new_size.u128() % contract_info.size_increment.u128()).ne(&0) {
+        return Err(ContractError::InvalidFields {
             fields: vec![String::from("size")],
-        })?;
+        });
+    }
+
+    let ask_price = Decimal::from_str(&new_price).map_err(|_| ContractError::InvalidFields {
+        fields: vec![String::from("price")],
+    })?;
+
+    if ask_price.is_zero()
+        || ask_price.is_sign_negative()
+        || is_invalid_price_precision(ask_price, contract_info.price_precision)
+    {
+        return Err(ContractError::InvalidFields {
+            fields: vec![String::from("price")],
+        });
+    }
+
+    let new_book_price = book_price(&new_price, contract_info.price_precision)?;
+    let price_changed = new_book_price.ne(&book_price(
+        &ask_order.price,
+        contract_info.price_precision,
+    )?);
+
+    // amendments aren't auto matched, so a new price may not cross a bid the ask could match
+    if price_changed
+        && ask_matchable(deps.storage, &env, &ask_order)?
+        && crosses_resting_bid(deps.as_ref(), &env, &ask_order, new_book_price)?
+    {
+        return Err(ContractError::InvalidFields {
+            fields: vec![String::from("price")],
+        });
+    }
 
     // is ask base a marker
     let is_base_restricted_marker = is_restricted_marker(&deps.querier, ask_order.base.clone());
 
     let mut response = Response::new();
 
-    // return 'base' to owner, return converted_base to issuer if applicable
-    response = add_transfer(
-        response,
-        is_base_restricted_marker,
-        effective_cancel_size.into(),
-        ask_order.base.to_owned(),
-        ask_order.owner.to_owned(),
-        env.contract.address.to_owned(),
-        env.contract.address.to_owned(),
-    );
-
-    response = response.add_attributes(vec![
-        attr("action", action.to_string()),
-        attr("id", id),
-        attr("reverse_size", effective_cancel_size),
-    ]);
+    // escrow additional base, or return the base no longer offered
+    if new_size.gt(&ask_order.size) {
+        let increase = new_size - ask_order.size;
+        if is_base_restricted_marker {
+            if !info.funds.is_empty() {
+                return Err(ContractError::SentFundsOrderMismatch);
+            }
+            response = response.add_message(transfer_marker_coins(
+                increase.into(),
+                ask_order.base.to_owned(),
+                env.contract.address.to_owned(),
+                ask_order.owner.to_owned(),
+                env.contract.address.to_owned(),
+            )?);
+        } else if info
+            .funds
+            .ne(&coins(increase.into(), ask_order.base.to_owned()))
+        {
+            return Err(ContractError::SentFundsOrderMismatch);
+        }
+    } else {
+        if !info.funds.is_empty() {
+            return Err(ContractError::SentFundsOrderMismatch);
+        }
+        let decrease = ask_order.size - new_size;
+        if !decrease.is_zero() {
+            response = add_transfer(
+                response,
+                is_base_restricted_marker,
+                decrease.u128(),
+                ask_order.base.to_owned(),
+                ask_order.owner.to_owned(),
+                env.contract.address.to_owned(),
+                env.contract.address.to_owned(),
+            );
+        }
+    }
 
-    if let AskOrderClass::Convertible {
-        status: AskOrderStatus::Ready {
-            approver,
-            converted_base,
-        },
-    } = ask_order.class.to_owned()
-    {
-        // is convertible a marker
-        let is_convertible_restricted_marker =
-            is_restricted_marker(&deps.querier, converted_base.denom.clone());
+    // a smaller order keeps its place in the queue, any other change moves it to the back
+    let keeps_priority = !price_changed && new_size.le(&ask_order.size);
 
-        response = add_transfer(
-            response,
-            is_convertible_restricted_marker,
-            effective_cancel_size.into(),
-            converted_base.denom,
-            approver,
-            env.contract.address.to_owned(),
-            env.contract.address,
-        );
-    }
+    ask_order.price = new_price;
+    ask_order.size = new_size;
+    ASKS_V1.save(deps.storage, ask_order.id.as_bytes(), &ask_order)?;
 
-    // remove the ask order from storage if remaining size is 0, otherwise, store updated order
-    if ask_order.size.is_zero() {
-        ASKS_V1.remove(deps.storage, ask_order.id.as_bytes());
-        response = response.add_attributes(vec![attr("order_open", "false")]);
-    } else {
-        ASKS_V1.save(deps.storage, ask_order.id.as_bytes(), &ask_order)?;
-        response = response.add_attributes(vec![attr("order_open", "true")]);
+    if !keeps_priority {
+        let expires = ASK_POSITIONS
+            .may_load(deps.storage, ask_order.id.as_bytes())?
+            .and_then(|position| position.expires);
+        unindex_ask(deps.storage, &ask_order.id)?;
+        index_ask(deps.storage, &contract_info, &ask_order, expires)?;
     }
 
-    Ok(response)
+    Ok(response.add_attributes(vec![
+        attr("action", ContractAction::UpdateAsk.to_string()),
+        attr("id", &ask_order.id),
+        attr("price", &ask_order.price),
+        attr("size", &ask_order.size.to_string()),
+    ]))
 }
 
-// reverse bid entrypoint
-fn reverse_bid(
+// update bid entrypoint, amends the price or remaining size of a resting bid
+fn update_bid(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     id: String,
-    action: ContractAction,
-    cancel_size: Option<Uint128>,
+    price: Option<String>,
+    size: Option<Uint128>,
 ) -> Result<Response, ContractError> {
-    // return error if id is empty
-    if id.is_empty() {
-        return Err(ContractError::Unauthorized);
-    }
-
-    // return error if funds sent
-    if !info.funds.is_empty() {
-        return Err(ContractError::ExpireWithFunds);
+    if price.is_none() && size.is_none() {
+        return Err(ContractError::InvalidFields {
+            fields: vec![String::from("price"), String::from("size")],
+        });
     }
 
     let contract_info = get_contract_info(deps.storage)?;
 
-    //load the bid order
     let mut bid_order = BIDS_V3
         .load(deps.storage, id.as_bytes())
         .map_err(|error| ContractError::LoadOrderFailed { error })?;
 
-    if action.eq(&ContractAction::CancelBid) {
-        if !info.sender.eq(&bid_order.owner) {
-            return Err(ContractError::Unauthorized);
-        }
-    } else if !contract_info.executors.contains(&info.sender) {
+    if !info.sender.eq(&bid_order.owner) {
         return Err(ContractError::Unauthorized);
     }
 
-    // determine the effective cancel size
-    let effective_cancel_size = match cancel_size {
-        None => bid_order.get_remaining_base(),
-        Some(cancel_size) => cancel_size,
-    };
+    if order_expired(deps.storage, &env, &BID_POSITIONS, &id)? {
+        return Err(ContractError::OrderExpired { id });
+    }
 
-    // error if cancel size is not multiple of size_increment
-    if (effective_cancel_size.u128() % contract_info.size_increment.u128()).ne(&0) {
+    let remaining_base = bid_order.get_remaining_base();
+    let new_price = price.unwrap_or_else(|| bid_order.price.to_owned());
+    let new_size = size.unwrap_or(remaining_base);
+
+    // error if new size is zero or not multiple of size_increment
+    if new_size.is_zero() || (new_size.u128() % contract_info.size_increment.u128()).ne(&0) {
         return Err(ContractError::InvalidFields {
             fields: vec![String::from("size")],
         });
     }
 
-    // error if cancel size is greater than available base size
-    if bid_order.get_remaining_base().lt(&effective_cancel_size) {
+    let bid_price = Decimal::from_str(&new_price).map_err(|_| ContractError::InvalidFields {
+        fields: vec![String::from("price")],
+    })?;
+
+    if bid_price.is_zero()
+        || bid_price.is_sign_negative()
+        || is_invalid_price_precision(bid_price, contract_info.price_precision)
+    {
         return Err(ContractError::InvalidFields {
-            fields: vec![String::from("size")],
+            fields: vec![String::from("price")],
         });
     }
 
-    // calculate canceled quote size (price * effective_cancel_size), error if overflows
-    let effective_cancel_quote_size = Decimal::from_str(&bid_order.price)
-        .unwrap()
-        .checked_mul(Decimal::from(effective_cancel_size.u128()))
+    let new_book_price = book_price(&new_price, contract_info.price_precision)?;
+    let price_changed = new_book_price.ne(&book_price(
+        &bid_order.price,
+        contract_info.price_precision,
+    )?);
+
+    // amendments aren't auto matched, so a new price may not cross an ask the bid could match
+    if price_changed && crosses_resting_ask(deps.as_ref(), &env, &bid_order, new_book_price)? {
+        return Err(ContractError::InvalidFields {
+            fields: vec![String::from("price")],
+        });
+    }
+
+    // calculate the new remaining quote (price * size), error if overflows
+    let remaining_quote = bid_price
+        .checked_mul(Decimal::from(new_size.u128()))
         .ok_or(ContractError::TotalOverflow)?;
 
-    // error if canceled quote total is not an integer
-    if effective_cancel_quote_size.fract().ne(&Decimal::zero()) {
+    // error if remaining quote is not an integer
+    if remaining_quote.fract().ne(&Decimal::zero()) {
         return Err(ContractError::NonIntegerTotal);
     }
 
-    let effective_cancel_quote_size = Uint128::new(effective_cancel_quote_size.to_u128().unwrap());
-
-    // calculate canceled fee size
-    let effective_cancel_fee_size = match &bid_order.fee {
-        Some(bid_fee) => {
-            let quote_remaining_ratio = bid_order
-                .get_quote_ratio(bid_order.get_remaining_quote() - effective_cancel_quote_size);
+    let remaining_quote = Uint128::new(
+        remaining_quote
+            .to_u128()
+            .ok_or(ContractError::TotalOverflow)?,
+    );
 
-            // fees required for remaining quote
-            let required_remaining_fees = Decimal::from_u128(bid_fee.amount.u128())
-                .unwrap()
-                .checked_mul(quote_remaining_ratio)
-                .ok_or(ContractError::TotalOverflow)?
-                .round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero)
-                .to_u128()
-                .unwrap();
+    let escrowed_fee = match &bid_order.fee {
+        Some(_) => bid_order.get_remaining_fee(),
+        None => Uint128::zero(),
+    };
 
-            // available fees - fees required = canceled/returned fees
-            let effective_cancel_fee_size = bid_order
-                .get_remaining_fee()
-                .checked_sub(required_remaining_fees.into())
-                .map_err(|_| ContractError::InvalidFields {
-                    fields: vec![String::from("size")],
-                })?;
+    // a new price is charged the current fee rate, otherwise the escrowed fee scales with size
+    let remaining_fee = if price_changed {
+        let bid_fee_rate =
+            bid_fee_escrow_rate(deps.as_ref(), &env, &contract_info, &bid_order.owner)?;
 
-            Some(Coin {
-                amount: effective_cancel_fee_size,
-                denom: bid_order.quote.denom.to_owned(),
-            })
-        }
-        _ => None,
-    };
+        bid_fee_rate.checked_mul(Decimal::from(remaining_quote.u128()))
+    } else {
+        Decimal::from(escrowed_fee.u128())
+            .checked_mul(Decimal::from(new_size.u128()))
+            .and_then(|fee| fee.checked_div(Decimal::from(remaining_base.u128())))
+    }
+    .ok_or(ContractError::TotalOverflow)?
+    .round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero)
+    .to_u128()
+    .ok_or(ContractError::TotalOverflow)?;
+    let remaining_fee = Uint128::new(remaining_fee);
 
     // is bid quote a marker
     let is_quote_restricted_marker =
         is_restricted_marker(&deps.querier, bid_order.quote.denom.clone());
 
-    bid_order.update_remaining_amounts(&Action::Reject {
-        base: Coin {
-            amount: effective_cancel_size,
-            denom: bid_order.base.denom.to_owned(),
-        },
-        fee: effective_cancel_fee_size.to_owned(),
-        quote: Coin {
-            amount: effective_cancel_quote_size,
-            denom: bid_order.quote.denom.to_owned(),
-        },
-    })?;
+    let escrowed = bid_order.get_remaining_quote() + escrowed_fee;
+    let required = remaining_quote + remaining_fee;
 
     let mut response = Response::new();
 
-    // 'send quote back to owner' message
-    response = add_transfer(
-        response,
-        is_quote_restricted_marker.to_owned(),
-        effective_cancel_quote_size.u128(),
-        bid_order.quote.denom.to_owned(),
-        bid_order.owner.to_owned(),
-        env.contract.address.to_owned(),
-        env.contract.address.to_owned(),
-    );
-
-    response = response.add_attributes(vec![
-        attr("action", action.to_string()),
-        attr("id", id),
-        attr("reverse_size", effective_cancel_size),
-    ]);
-
-    // add 'send fee back to owner' message
-    if let Some(fee) = effective_cancel_fee_size {
-        if fee.amount.gt(&Uint128::zero()) {
+    // escrow additional quote and fee, or return what is no longer required
+    if required.gt(&escrowed) {
+        let increase = required - escrowed;
+        if is_quote_restricted_marker {
+            if !info.funds.is_empty() {
+                return Err(ContractError::SentFundsOrderMismatch);
+            }
+            response = response.add_message(transfer_marker_coins(
+                increase.into(),
+                bid_order.quote.denom.to_owned(),
+                env.contract.address.to_owned(),
+                bid_order.owner.to_owned(),
+                env.contract.address.to_owned(),
+            )?);
+        } else if info
+            .funds
+            .ne(&coins(increase.into(), bid_order.quote.denom.to_owned()))
+        {
+            return Err(ContractError::SentFundsOrderMismatch);
+        }
+    } else {
+        if !info.funds.is_empty() {
+            return Err(ContractError::SentFundsOrderMismatch);
+        }
+        let decrease = escrowed - required;
+        if !decrease.is_zero() {
             response = add_transfer(
                 response,
                 is_quote_restricted_marker,
-                fee.amount.u128(),
+                decrease.u128(),
                 bid_order.quote.denom.to_owned(),
                 bid_order.owner.to_owned(),
                 env.contract.address.to_owned(),
-                env.contract.address,
+                env.contract.address.to_owned(),
             );
         }
     }
 
-    // remove the bid order from storage if remaining size is 0, otherwise, store updated order
-    match bid_order.get_remaining_base().is_zero() {
-        true => {
-            BIDS_V3.remove(deps.storage, bid_order.id.as_bytes());
-            response = response.add_attributes(vec![attr("order_open", "false")]);
-        }
-        false => {
-            BIDS_V3.save(deps.storage, bid_order.id.as_bytes(), &bid_order)?;
-            response = response.add_attributes(vec![attr("order_open", "true")]);
-        }
+    // order totals are what has been accumulated so far plus the amended remainder
+    bid_order.price = new_price;
+    bid_order.base.amount = bid_order.accumulated_base + new_size;
+    bid_order.quote.amount = bid_order.accumulated_quote + remaining_quote;
+    bid_order.fee = match (&bid_order.fee, remaining_fee.is_zero()) {
+        (None, true) => None,
+        _ => Some(Coin {
+            amount: bid_order.accumulated_fee + remaining_fee,
+            denom: bid_order.quote.denom.to_owned(),
+        }),
+    };
+    BIDS_V3.save(deps.storage, bid_order.id.as_bytes(), &bid_order)?;
+
+    // a smaller order keeps its place in the queue, any other change moves it to the back
+    if price_changed || new_size.gt(&remaining_base) {
+        let expires = BID_POSITIONS
+            .may_load(deps.storage, bid_order.id.as_bytes())?
+            .and_then(|position| position.expires);
+        unindex_bid(deps.storage, &bid_order.id)?;
+        index_bid(deps.storage, &contract_info, &bid_order, expires)?;
     }
 
-    Ok(response)
+    Ok(response.add_attributes(vec![
+        attr("action", ContractAction::UpdateBid.to_string()),
+        attr("id", &bid_order.id),
+        attr("price", &bid_order.price),
+        attr("size", &new_size.to_string()),
+        attr("quote_size", &remaining_quote.to_string()),
+        attr("fee", &remaining_fee.to_string()),
+    ]))
 }
 
-// match and execute an ask and bid order
-fn execute_match(
+// set or clear the maker/taker fee schedule, which replaces the flat ask and bid fee rates
+fn set_fee_schedule(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    ask_id: String,
-    bid_id: String,
-    price: String,
-    execute_size: Uint128,
+    fee_schedule: Option<FeeSchedule>,
 ) -> Result<Response, ContractError> {
-    let contract_info = get_contract_info(deps.storage)?;
-
-    // only executors may execute matches
-    if !contract_info.executors.contains(&info.sender) {
+    // only the contract admin may change fees
+    let admin = deps
+        .querier
+        .query_wasm_contract_info(env.contract.address)?
+        .admin;
+    if admin.ne(&Some(info.sender.to_string())) {
         return Err(ContractError::Unauthorized);
     }
 
-    // return error if funds sent
-    if !info.funds.is_empty() {
-        return Err(ContractError::ExecuteWithFunds);
-    }
-
-    let mut ask_order = ASKS_V1
-        .load(deps.storage, ask_id.as_bytes())
-        .map_err(|error| ContractError::LoadOrderFailed { error })?;
-
-    let mut bid_order = BIDS_V3
-        .load(deps.storage, bid_id.as_bytes())
-        .map_err(|error| ContractError::LoadOrderFailed { error })?;
+    let mut response =
+        Response::new().add_attribute("action", ContractAction::SetFeeSchedule.to_string());
+
+    match fee_schedule {
+        Some(mut fee_schedule) => {
+            deps.api.addr_validate(fee_schedule.account.as_str())?;
+
+            // every rate and discount must be a fraction between 0 and 1
+            let rates = [&fee_schedule.maker_rate, &fee_schedule.taker_rate]
+                .into_iter()
+                .chain(
+                    fee_schedule
+                        .tiers
+                        .iter()
+                        .flat_map(|tier| [&tier.maker_rate, &tier.taker_rate]),
+                )
+                .chain(fee_schedule.discounts.iter().map(|discount| &discount.rate));
+            for rate in rates {
+                match Decimal::from_str(rate) {
+                    Ok(rate) if !rate.is_sign_negative() && rate.le(&Decimal::ONE) => (),
+                    _ => {
+                        return Err(ContractError::InvalidFields {
+                            fields: vec![String::from("fee_schedule")],
+                        })
+                    }
+                }
+            }
 
-    // Validate the requested quote denom in the ask order matches the offered quote denom in the bid order
-    if ask_order.quote.ne(&bid_order.quote.denom) {
-        return Err(ContractError::UnsupportedQuoteDenom);
+            fee_schedule.tiers.sort_by_key(|tier| tier.min_volume);
+            FEE_SCHEDULE.save(deps.storage, &fee_schedule)?;
+            response = response.add_attribute("fee_schedule", format!("{:?}", fee_schedule));
+        }
+        None => FEE_SCHEDULE.remove(deps.storage),
     }
 
-    let ask_price =
-        Decimal::from_str(&ask_order.price).map_err(|_| ContractError::InvalidFields {
-            fields: vec![String::from("AskOrder.price")],
-        })?;
-
-    let bid_price =
-        Decimal::from_str(&bid_order.price).map_err(|_| ContractError::InvalidFields {
-            fields: vec![String::from("BidOrder.price")],
-        })?;
-
-    let execute_price = Decimal::from_str(&price).map_err(|_| ContractError::InvalidFields {
-        fields: vec![String::from("ExecuteMsg.price")],
-    })?;
+    Ok(response)
+}
 
-    match ask_price.cmp(&bid_price) {
-        // order prices overlap, use ask or bid price determined by execute msg provided price
-        Ordering::Less => {
-            if execute_price.ne(&ask_price) && execute_price.ne(&bid_price) {
-                return Err(ContractError::InvalidExecutePrice);
-            }
-        }
-        // if order prices are equal, execute price should be equal
-        Ordering::Equal => {
-            if execute_price.ne(&ask_price) {
-                return Err(ContractError::InvalidExecutePrice);
-            }
-        }
-        // ask price is greater than bid price, normal price spread behavior and should not match
-        Ordering::Greater => {
-            return Err(ContractError::AskBidPriceMismatch);
-        }
+// cancel ask entrypoint
+fn cancel_ask(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: String,
+) -> Result<Response, ContractError> {
+    // return error if funds sent
+    if !info.funds.is_empty() {
+        return Err(ContractError::CancelWithFunds);
     }
 
-    // at least one side of the order will always execute fully, both sides if order sizes equal
-    // so the provided execute match size must be either the ask or bid size (or both if equal)
-    if execute_size.gt(&ask_order.size) || execute_size.gt(&bid_order.get_remaining_base()) {
-        return Err(ContractError::InvalidExecuteSize);
+    let AskOrderV1 {
+        id,
+        owner,
+        class,
+        base,
+        size,
+        ..
+    } = ASKS_V1
+        .load(deps.storage, id.as_bytes())
+        .map_err(|error| ContractError::LoadOrderFailed { error })?;
+    if !info.sender.eq(&owner) {
+        return Err(ContractError::Unauthorized);
     }
 
-    // calculate gross proceeds using execute price, (price * size), error if overflows
-    let actual_gross_proceeds = execute_price
-        .checked_mul(Decimal::from(execute_size.u128()))
-        .ok_or(ContractError::TotalOverflow)?;
+    // remove the ask order from storage
+    ASKS_V1.remove(deps.storage, id.as_bytes());
+    unindex_ask(deps.storage, &id)?;
 
-    // error if gross proceeds is not an integer
-    if actual_gross_proceeds.fract().ne(&Decimal::zero()) {
-        return Err(ContractError::NonIntegerTotal);
-    }
+    // is ask base a marker
+    let is_base_restricted_marker = is_restricted_marker(&deps.querier, base.clone());
 
-    let mut net_proceeds = Uint128::new(
-        actual_gross_proceeds
-            .to_u128()
-            .ok_or(ContractError::TotalOverflow)?,
+    let mut response = Response::new();
+
+    // return 'base' to owner, return converted_base to issuer if applicable
+    response = add_transfer(
+        response,
+        is_base_restricted_marker,
+        size.into(),
+        base,
+        owner,
+        env.contract.address.to_owned(),
+        env.contract.address.to_owned(),
     );
 
-    ask_order.size -= execute_size;
+    response = response.add_attributes(vec![
+        attr("action", ContractAction::CancelAsk.to_string()),
+        attr("id", id),
+    ]);
 
     if let AskOrderClass::Convertible {
-        status: AskOrderStatus::Ready { converted_base, .. },
-    } = &mut ask_order.class
+        status: AskOrderStatus::Ready {
+            approver,
+            converted_base,
+        },
+    } = class
     {
-        converted_base.amount = ask_order.size;
+        // is convertible a marker
+        let is_convertible_restricted_marker =
+            is_restricted_marker(&deps.querier, converted_base.denom.clone());
+
+        response = add_transfer(
+            response,
+            is_convertible_restricted_marker,
+            converted_base.amount.into(),
+            converted_base.denom,
+            approver,
+            env.contract.address.to_owned(),
+            env.contract.address,
+        );
+    }
+
+    Ok(response)
+}
+
+// reverse ask entrypoint
+fn reverse_ask(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: String,
+    action: ContractAction,
+    cancel_size: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    // return error if id is empty
+    if id.is_empty() {
+        return Err(ContractError::Unauthorized);
+    }
+
+    // return error if funds sent
+    if !info.funds.is_empty() {
+        return Err(ContractError::ExpireWithFunds);
+    }
+
+    let contract_info = get_contract_info(deps.storage)?;
+
+    if !contract_info.executors.contains(&info.sender) {
+        return Err(ContractError::Unauthorized);
+    }
+
+    reverse_ask_order(deps, env, id, action, cancel_size)
+}
+
+// return all or part of an ask order's escrow to its owner
+fn reverse_ask_order(
+    deps: DepsMut,
+    env: Env,
+    id: String,
+    action: ContractAction,
+    cancel_size: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let contract_info = get_contract_info(deps.storage)?;
+
+    // retrieve the order
+    let mut ask_order = ASKS_V1
+        .load(deps.storage, id.as_bytes())
+        .map_err(|error| ContractError::LoadOrderFailed { error })?;
+
+    // determine the effective cancel size
+    let effective_cancel_size = match cancel_size {
+        None => ask_order.size,
+        Some(cancel_size) => cancel_size,
+    };
+
+    // error if cancel size is not multiple of size_increment
+    if (effective_cancel_size.u128() % contract_info.size_increment.u128()).ne(&0) {
+        return Err(ContractError::InvalidFields {
+            fields: vec![String::from("size")],
+        });
+    }
+
+    // subtract the cancel size from the order size
+    ask_order.size = ask_order
+        .size
+        .checked_sub(effective_cancel_size)
+        .map_err(|_| ContractError::InvalidFields {
+            fields: vec![String::from("size")],
+        })?;
+
+    // is ask base a marker
+    let is_base_restricted_marker = is_restricted_marker(&deps.querier, ask_order.base.clone());
+
+    let mut response = Response::new();
+
+    // return 'base' to owner, return converted_base to issuer if applicable
+    response = add_transfer(
+        response,
+        is_base_restricted_marker,
+        effective_cancel_size.into(),
+        ask_order.base.to_owned(),
+        ask_order.owner.to_owned(),
+        env.contract.address.to_owned(),
+        env.contract.address.to_owned(),
+    );
+
+    response = response.add_attributes(vec![
+        attr("action", action.to_string()),
+        attr("id", id),
+        attr("reverse_size", effective_cancel_size),
+    ]);
+
+    if let AskOrderClass::Convertible {
+        status: AskOrderStatus::Ready {
+            approver,
+            converted_base,
+        },
+    } = ask_order.class.to_owned()
+    {
+        // is convertible a marker
+        let is_convertible_restricted_marker =
+            is_restricted_marker(&deps.querier, converted_base.denom.clone());
+
+        response = add_transfer(
+            response,
+            is_convertible_restricted_marker,
+            effective_cancel_size.into(),
+            converted_base.denom,
+            approver,
+            env.contract.address.to_owned(),
+            env.contract.address,
+        );
+    }
+
+    // remove the ask order from storage if remaining size is 0, otherwise, store updated order
+    if ask_order.size.is_zero() {
+        ASKS_V1.remove(deps.storage, ask_order.id.as_bytes());
+        unindex_ask(deps.storage, &ask_order.id)?;
+        response = response.add_attributes(vec![attr("order_open", "false")]);
+    } else {
+        ASKS_V1.save(deps.storage, ask_order.id.as_bytes(), &ask_order)?;
+        response = response.add_attributes(vec![attr("order_open", "true")]);
+    }
+
+    Ok(response)
+}
+
+// reverse bid entrypoint
+fn reverse_bid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: String,
+    action: ContractAction,
+    cancel_size: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    // return error if id is empty
+    if id.is_empty() {
+        return Err(ContractError::Unauthorized);
+    }
+
+    // return error if funds sent
+    if !info.funds.is_empty() {
+        return Err(ContractError::ExpireWithFunds);
+    }
+
+    let contract_info = get_contract_info(deps.storage)?;
+
+    //load the bid order
+    let bid_order = BIDS_V3
+        .load(deps.storage, id.as_bytes())
+        .map_err(|error| ContractError::LoadOrderFailed { error })?;
+
+    if action.eq(&ContractAction::CancelBid) {
+        if !info.sender.eq(&bid_order.owner) {
+            return Err(ContractError::Unauthorized);
+        }
+    } else if !contract_info.executors.contains(&info.sender) {
+        return Err(ContractError::Unauthorized);
+    }
+
+    reverse_bid_order(deps, env, id, action, cancel_size)
+}
+
+// return all or part of a bid order's escrowed quote and fee to its owner
+fn reverse_bid_order(
+    deps: DepsMut,
+    env: Env,
+    id: String,
+    action: ContractAction,
+    cancel_size: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let contract_info = get_contract_info(deps.storage)?;
+
+    let mut bid_order = BIDS_V3
+        .load(deps.storage, id.as_bytes())
+        .map_err(|error| ContractError::LoadOrderFailed { error })?;
+
+    // determine the effective cancel size
+    let effective_cancel_size = match cancel_size {
+        None => bid_order.get_remaining_base(),
+        Some(cancel_size) => cancel_size,
+    };
+
+    // error if cancel size is not multiple of size_increment
+    if (effective_cancel_size.u128() % contract_info.size_increment.u128()).ne(&0) {
+        return Err(ContractError::InvalidFields {
+            fields: vec![String::from("size")],
+        });
+    }
+
+    // error if cancel size is greater than available base size
+    if bid_order.get_remaining_base().lt(&effective_cancel_size) {
+        return Err(ContractError::InvalidFields {
+            fields: vec![String::from("size")],
+        });
+    }
+
+    // calculate canceled quote size (price * effective_cancel_size), error if overflows
+    let effective_cancel_quote_size = Decimal::from_str(&bid_order.price)
+        .unwrap()
+        .checked_mul(Decimal::from(effective_cancel_size.u128()))
+        .ok_or(ContractError::TotalOverflow)?;
+
+    // error if canceled quote total is not an integer
+    if effective_cancel_quote_size.fract().ne(&Decimal::zero()) {
+        return Err(ContractError::NonIntegerTotal);
+    }
+
+    let effective_cancel_quote_size = Uint128::new(effective_cancel_quote_size.to_u128().unwrap());
+
+    // calculate canceled fee size
+    let effective_cancel_fee_size = match &bid_order.fee {
+        Some(bid_fee) => {
+            let quote_remaining_ratio = bid_order
+                .get_quote_ratio(bid_order.get_remaining_quote() - effective_cancel_quote_size);
+
+            // fees required for remaining quote
+            let required_remaining_fees = Decimal::from_u128(bid_fee.amount.u128())
+                .unwrap()
+                .checked_mul(quote_remaining_ratio)
+                .ok_or(ContractError::TotalOverflow)?
+                .round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero)
+                .to_u128()
+                .unwrap();
+
+            // available fees - fees required = canceled/returned fees
+            let effective_cancel_fee_size = bid_order
+                .get_remaining_fee()
+                .checked_sub(required_remaining_fees.into())
+                .map_err(|_| ContractError::InvalidFields {
+                    fields: vec![String::from("size")],
+                })?;
+
+            Some(Coin {
+                amount: effective_cancel_fee_size,
+                denom: bid_order.quote.denom.to_owned(),
+            })
+        }
+        _ => None,
+    };
+
+    // is bid quote a marker
+    let is_quote_restricted_marker =
+        is_restricted_marker(&deps.querier, bid_order.quote.denom.clone());
+
+    bid_order.update_remaining_amounts(&Action::Reject {
+        base: Coin {
+            amount: effective_cancel_size,
+            denom: bid_order.base.denom.to_owned(),
+        },
+        fee: effective_cancel_fee_size.to_owned(),
+        quote: Coin {
+            amount: effective_cancel_quote_size,
+            denom: bid_order.quote.denom.to_owned(),
+        },
+    })?;
+
+    let mut response = Response::new();
+
+    // 'send quote back to owner' message
+    response = add_transfer(
+        response,
+        is_quote_restricted_marker.to_owned(),
+        effective_cancel_quote_size.u128(),
+        bid_order.quote.denom.to_owned(),
+        bid_order.owner.to_owned(),
+        env.contract.address.to_owned(),
+        env.contract.address.to_owned(),
+    );
+
+    response = response.add_attributes(vec![
+        attr("action", action.to_string()),
+        attr("id", id),
+        attr("reverse_size", effective_cancel_size),
+    ]);
+
+    // add 'send fee back to owner' message
+    if let Some(fee) = effective_cancel_fee_size {
+        if fee.amount.gt(&Uint128::zero()) {
+            response = add_transfer(
+                response,
+                is_quote_restricted_marker,
+                fee.amount.u128(),
+                bid_order.quote.denom.to_owned(),
+                bid_order.owner.to_owned(),
+                env.contract.address.to_owned(),
+                env.contract.address,
+            );
+        }
+    }
+
+    // remove the bid order from storage if remaining size is 0, otherwise, store updated order
+    match bid_order.get_remaining_base().is_zero() {
+        true => {
+            BIDS_V3.remove(deps.storage, bid_order.id.as_bytes());
+            unindex_bid(deps.storage, &bid_order.id)?;
+            response = response.add_attributes(vec![attr("order_open", "false")]);
+        }
+        false => {
+            BIDS_V3.save(deps.storage, bid_order.id.as_bytes(), &bid_order)?;
+            response = response.add_attributes(vec![attr("order_open", "true")]);
+        }
+    }
+
+    Ok(response)
+}
+
+// match and execute an ask and bid order
+fn execute_match(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    ask_id: String,
+    bid_id: String,
+    price: String,
+    execute_size: Uint128,
+) -> Result<Response, ContractError> {
+    let contract_info = get_contract_info(deps.storage)?;
+
+    // only executors may execute matches
+    if !contract_info.executors.contains(&info.sender) {
+        return Err(ContractError::Unauthorized);
+    }
+
+    // return error if funds sent
+    if !info.funds.is_empty() {
+        return Err(ContractError::ExecuteWithFunds);
+    }
+
+    settle_match(deps, env, ask_id, bid_id, price, execute_size)
+}
+
+// settle a fill between an ask and bid order, shared by executor and automatic matches
+fn settle_match(
+    deps: DepsMut,
+    env: Env,
+    ask_id: String,
+    bid_id: String,
+    price: String,
+    execute_size: Uint128,
+) -> Result<Response, ContractError> {
+    let contract_info = get_contract_info(deps.storage)?;
+
+    let mut ask_order = ASKS_V1
+        .load(deps.storage, ask_id.as_bytes())
+        .map_err(|error| ContractError::LoadOrderFailed { error })?;
+
+    let mut bid_order = BIDS_V3
+        .load(deps.storage, bid_id.as_bytes())
+        .map_err(|error| ContractError::LoadOrderFailed { error })?;
+
+    // good till time orders can't be matched once stale
+    if order_expired(deps.storage, &env, &ASK_POSITIONS, &ask_id)? {
+        return Err(ContractError::OrderExpired { id: ask_id });
+    }
+    if order_expired(deps.storage, &env, &BID_POSITIONS, &bid_id)? {
+        return Err(ContractError::OrderExpired { id: bid_id });
+    }
+
+    // the order that rested on the book first is the maker, every open order is indexed on
+    // creation or by migration
+    let ask_position = ASK_POSITIONS
+        .load(deps.storage, ask_id.as_bytes())
+        .map_err(|error| ContractError::LoadOrderFailed { error })?;
+    let bid_position = BID_POSITIONS
+        .load(deps.storage, bid_id.as_bytes())
+        .map_err(|error| ContractError::LoadOrderFailed { error })?;
+    let ask_is_maker = ask_position.sequence.lt(&bid_position.sequence);
+
+    let fee_schedule = FEE_SCHEDULE.may_load(deps.storage)?;
+
+    // Validate the requested quote denom in the ask order matches the offered quote denom in the bid order
+    if ask_order.quote.ne(&bid_order.quote.denom) {
+        return Err(ContractError::UnsupportedQuoteDenom);
+    }
+
+    let ask_price =
+        Decimal::from_str(&ask_order.price).map_err(|_| ContractError::InvalidFields {
+            fields: vec![String::from("AskOrder.price")],
+        })?;
+
+    let bid_price =
+        Decimal::from_str(&bid_order.price).map_err(|_| ContractError::InvalidFields {
+            fields: vec![String::from("BidOrder.price")],
+        })?;
+
+    let execute_price = Decimal::from_str(&price).map_err(|_| ContractError::InvalidFields {
+        fields: vec![String::from("ExecuteMsg.price")],
+    })?;
+
+    match ask_price.cmp(&bid_price) {
+        // order prices overlap, use ask or bid price determined by execute msg provided price
+        Ordering::Less => {
+            if execute_price.ne(&ask_price) && execute_price.ne(&bid_price) {
+                return Err(ContractError::InvalidExecutePrice);
+            }
+        }
+        // if order prices are equal, execute price should be equal
+        Ordering::Equal => {
+            if execute_price.ne(&ask_price) {
+                return Err(ContractError::InvalidExecutePrice);
+            }
+        }
+        // ask price is greater than bid price, normal price spread behavior and should not match
+        Ordering::Greater => {
+            return Err(ContractError::AskBidPriceMismatch);
+        }
+    }
+
+    // at least one side of the order will always execute fully, both sides if order sizes equal
+    // so the provided execute match size must be either the ask or bid size (or both if equal)
+    if execute_size.gt(&ask_order.size) || execute_size.gt(&bid_order.get_remaining_base()) {
+        return Err(ContractError::InvalidExecuteSize);
+    }
+
+    // calculate gross proceeds using execute price, (price * size), error if overflows
+    let actual_gross_proceeds = execute_price
+        .checked_mul(Decimal::from(execute_size.u128()))
+        .ok_or(ContractError::TotalOverflow)?;
+
+    // error if gross proceeds is not an integer
+    if actual_gross_proceeds.fract().ne(&Decimal::zero()) {
+        return Err(ContractError::NonIntegerTotal);
+    }
+
+    let mut net_proceeds = Uint128::new(
+        actual_gross_proceeds
+            .to_u128()
+            .ok_or(ContractError::TotalOverflow)?,
+    );
+
+    ask_order.size -= execute_size;
+
+    if let AskOrderClass::Convertible {
+        status: AskOrderStatus::Ready { converted_base, .. },
+    } = &mut ask_order.class
+    {
+        converted_base.amount = ask_order.size;
+    }
+
+    // is base a restricted marker
+    let is_base_restricted_marker = is_restricted_marker(&deps.querier, ask_order.base.clone());
+
+    // is quote a restricted marker
+    let is_quote_restricted_marker =
+        is_restricted_marker(&deps.querier, bid_order.quote.denom.clone());
+
+    let mut response = Response::new();
+    response = response.add_attributes(vec![
+        attr("action", ContractAction::Execute.to_string()),
+        attr("ask_id", &ask_id),
+        attr("bid_id", &bid_id),
+        attr("base", &bid_order.base.denom),
+        attr("quote", &ask_order.quote),
+        attr("price", &execute_price.to_string()),
+        attr("size", &execute_size.to_string()),
+    ]);
+
+    // a fee schedule replaces the flat ask fee rate
+    let ask_fee_terms = match &fee_schedule {
+        Some(fee_schedule) => Some((
+            scheduled_fee_rate(
+                deps.as_ref(),
+                &env,
+                fee_schedule,
+                &ask_order.owner,
+                ask_is_maker,
+            )?,
+            fee_schedule.account.to_owned(),
+        )),
+        None => match contract_info.ask_fee_info {
+            Some(ask_fee_info) => Some((
+                Decimal::from_str(&ask_fee_info.rate).map_err(|_| {
+                    ContractError::InvalidFields {
+                        fields: vec![String::from("ContractInfo.ask_fee_info.rate")],
+                    }
+                })?,
+                ask_fee_info.account,
+            )),
+            None => None,
+        },
+    };
+
+    // calculate ask fees and create message if applicable
+    let ask_fee = match ask_fee_terms {
+        // calculate ask fee using total
+        Some((ask_fee_rate, ask_fee_account)) => {
+            match ask_fee_rate
+                .checked_mul(actual_gross_proceeds)
+                .ok_or(ContractError::TotalOverflow)?
+                .round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero)
+                .to_u128()
+                .ok_or(ContractError::TotalOverflow)?
+            {
+                0u128 => None,
+                fee_total => {
+                    let ask_fee = Coin {
+                        denom: bid_order.quote.denom.to_owned(),
+                        amount: Uint128::new(fee_total),
+                    };
+
+                    response = add_transfer(
+                        response,
+                        is_quote_restricted_marker.to_owned(),
+                        fee_total,
+                        bid_order.quote.denom.to_owned(),
+                        ask_fee_account,
+                        env.contract.address.to_owned(),
+                        env.contract.address.to_owned(),
+                    );
+
+                    // subtract the fees and assign to net proceeds
+                    net_proceeds =
+                        net_proceeds
+                            .checked_sub(Uint128::new(fee_total))
+                            .map_err(|error| {
+                                ContractError::Std(StdError::Overflow { source: error })
+                            })?;
+
+                    Some(ask_fee)
+                }
+            }
+        }
+        None => None,
+    };
+
+    response = response.add_attribute(
+        "ask_fee",
+        match ask_fee {
+            None => Uint128::zero(),
+            Some(fee) => fee.amount,
+        },
+    );
+
+    // calculate gross proceeds using bid price, (price * size), error if overflows
+    let original_gross_proceeds = bid_price
+        .checked_mul(Decimal::from(execute_size.u128()))
+        .ok_or(ContractError::TotalOverflow)?;
+
+    // error if gross proceeds is not an integer
+    if original_gross_proceeds.fract().ne(&Decimal::zero()) {
+        return Err(ContractError::NonIntegerTotal);
+    }
+
+    let original_gross_proceeds = Uint128::new(
+        original_gross_proceeds
+            .to_u128()
+            .ok_or(ContractError::TotalOverflow)?,
+    );
+
+    // get bid fees and create message if applicable
+    let actual_bid_fee = match (&bid_order.fee, &fee_schedule) {
+        // scheduled fees are capped at what the bid escrowed for this fill
+        (Some(_), Some(fee_schedule)) => {
+            let scheduled_fee = scheduled_fee_rate(
+                deps.as_ref(),
+                &env,
+                fee_schedule,
+                &bid_order.owner,
+                !ask_is_maker,
+            )?
+            .checked_mul(actual_gross_proceeds)
+            .ok_or(ContractError::TotalOverflow)?
+            .round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero)
+            .to_u128()
+            .ok_or(ContractError::TotalOverflow)?;
+
+            bid_order
+                .calculate_fee(original_gross_proceeds)?
+                .map(|mut bid_fee| {
+                    bid_fee.amount = bid_fee.amount.min(Uint128::new(scheduled_fee));
+                    bid_fee
+                })
+                .filter(|bid_fee| !bid_fee.amount.is_zero())
+        }
+        (Some(_), None) => bid_order.calculate_fee(Uint128::new(
+            actual_gross_proceeds
+                .to_u128()
+                .ok_or(ContractError::TotalOverflow)?,
+        ))?,
+        (None, _) => None,
+    };
+
+    let bid_fee_account = match &fee_schedule {
+        Some(fee_schedule) => Some(fee_schedule.account.to_owned()),
+        None => contract_info
+            .bid_fee_info
+            .map(|bid_fee_info| bid_fee_info.account),
+    };
+
+    // add bid fee message
+    match &actual_bid_fee {
+        Some(bid_fee) => match bid_fee_account {
+            Some(bid_fee_account) => {
+                response = add_transfer(
+                    response,
+                    is_quote_restricted_marker.to_owned(),
+                    bid_fee.amount.to_owned().u128(),
+                    bid_fee.denom.to_owned(),
+                    bid_fee_account,
+                    env.contract.address.to_owned(),
+                    env.contract.address.to_owned(),
+                );
+            }
+            None => return Err(ContractError::BidFeeAccountMissing),
+        },
+        None => (),
+    }
+
+    response = response.add_attribute(
+        "bid_fee",
+        match &actual_bid_fee {
+            None => Uint128::zero(),
+            Some(fee) => fee.amount,
+        },
+    );
+
+    // add 'send quote to asker' and 'send base to bidder' messages
+    match &ask_order.class {
+        AskOrderClass::Basic => {
+            response = add_transfer(
+                response,
+                is_quote_restricted_marker.to_owned(),
+                net_proceeds.into(),
+                bid_order.quote.denom.to_owned(),
+                ask_order.owner.to_owned(),
+                env.contract.address.to_owned(),
+                env.contract.address.to_owned(),
+            );
+            response = add_transfer(
+                response,
+                is_base_restricted_marker.to_owned(),
+                execute_size.into(),
+                ask_order.base.to_owned(),
+                bid_order.owner.to_owned(),
+                env.contract.address.to_owned(),
+                env.contract.address.to_owned(),
+            );
+        }
+        AskOrderClass::Convertible {
+            status:
+                AskOrderStatus::Ready {
+                    approver,
+                    converted_base,
+                },
+        } => {
+            response = add_transfer(
+                response,
+                is_base_restricted_marker.to_owned(),
+                execute_size.into(),
+                converted_base.to_owned().denom,
+                bid_order.owner.to_owned(),
+                env.contract.address.to_owned(),
+                env.contract.address.to_owned(),
+            );
+            response = add_transfer(
+                response,
+                is_base_restricted_marker,
+                execute_size.into(),
+                ask_order.base.to_owned(),
+                approver.to_owned(),
+                env.contract.address.to_owned(),
+                env.contract.address.to_owned(),
+            );
+
+            response = add_transfer(
+                response,
+                is_quote_restricted_marker.to_owned(),
+                net_proceeds.into(),
+                bid_order.quote.denom.clone(),
+                approver.to_owned(),
+                env.contract.address.to_owned(),
+                env.contract.address.to_owned(),
+            );
+        }
+        AskOrderClass::Convertible { status } => {
+            return Err(ContractError::AskOrderNotReady {
+                current_status: format!("{:?}", status),
+            });
+        }
+    };
+
+    // determine refunds to bidder, for a better execute price or a fee below the escrowed fee
+    let bid_quote_refund = original_gross_proceeds
+        .checked_sub(Uint128::new(
+            actual_gross_proceeds
+                .to_u128()
+                .ok_or(ContractError::TotalOverflow)?,
+        ))
+        .map_err(|error| ContractError::Std(StdError::Overflow { source: error }))?;
+
+    let bid_fee_refund = {
+        let original_bid_fee = bid_order.calculate_fee(original_gross_proceeds)?;
+
+        match (&actual_bid_fee, original_bid_fee) {
+            (Some(actual_bid_fee), Some(mut original_bid_fee)) => {
+                let refund_amount = original_bid_fee.amount - actual_bid_fee.amount;
+
+                if refund_amount.gt(&Uint128::zero()) {
+                    original_bid_fee.amount = refund_amount;
+                    Some(original_bid_fee)
+                } else {
+                    None
+                }
+            }
+            // nothing was charged, so the whole escrowed fee for this fill is returned
+            (None, Some(original_bid_fee)) if !original_bid_fee.amount.is_zero() => {
+                Some(original_bid_fee)
+            }
+            (_, _) => None,
+        }
+    };
+
+    if bid_quote_refund.gt(&Uint128::zero()) {
+        response = add_transfer(
+            response,
+            is_quote_restricted_marker.to_owned(),
+            bid_quote_refund.u128(),
+            bid_order.quote.denom.to_owned(),
+            bid_order.owner.to_owned(),
+            env.contract.address.to_owned(),
+            env.contract.address.to_owned(),
+        );
+    }
+    if let Some(fee_refund) = &bid_fee_refund {
+        response = add_transfer(
+            response,
+            is_quote_restricted_marker,
+            fee_refund.amount.u128(),
+            fee_refund.denom.to_owned(),
+            bid_order.owner.to_owned(),
+            env.contract.address.to_owned(),
+            env.contract.address.to_owned(),
+        );
+    }
+
+    bid_order.update_remaining_amounts(&Action::Fill {
+        base: Coin {
+            denom: bid_order.base.denom.to_owned(),
+            amount: execute_size,
+        },
+        fee: actual_bid_fee,
+        price,
+        quote: Coin {
+            denom: bid_order.quote.denom.to_owned(),
+            amount: Uint128::new(
+                actual_gross_proceeds
+                    .to_u128()
+                    .ok_or(ContractError::TotalOverflow)?,
+            ),
+        },
+    })?;
+
+    if bid_quote_refund.gt(&Uint128::zero()) || bid_fee_refund.is_some() {
+        bid_order.update_remaining_amounts(&Action::Refund {
+            fee: bid_fee_refund,
+            quote: Coin {
+                denom: bid_order.quote.denom.to_owned(),
+                amount: bid_quote_refund,
+            },
+        })?;
+    }
+
+    // both sides count the filled base toward their trailing volume, which only sets scheduled fees
+    if fee_schedule.is_some() {
+        record_volume(deps.storage, &env, &ask_order.owner, execute_size)?;
+        record_volume(deps.storage, &env, &bid_order.owner, execute_size)?;
+    }
+
+    // finally update or remove the orders from storage
+    if ask_order.size.is_zero() {
+        ASKS_V1.remove(deps.storage, ask_id.as_bytes());
+        unindex_ask(deps.storage, &ask_id)?;
+    } else {
+        ASKS_V1.update(deps.storage, ask_id.as_bytes(), |_| -> StdResult<_> {
+            Ok(ask_order)
+        })?;
+    }
+
+    if bid_order.get_remaining_base().eq(&Uint128::zero()) {
+        BIDS_V3.remove(deps.storage, bid_id.as_bytes());
+        unindex_bid(deps.storage, &bid_id)?;
+    } else {
+        BIDS_V3.update(deps.storage, bid_id.as_bytes(), |_| -> StdResult<_> {
+            Ok(bid_order)
+        })?;
+    }
+
+    Ok(response)
+}
+
+// cross a new ask against resting bids, best price first and oldest first within a price
+fn match_incoming_ask(
+    mut deps: DepsMut,
+    env: Env,
+    ask_id: &str,
+) -> Result<Response, ContractError> {
+    let contract_info = get_contract_info(deps.storage)?;
+    let ask_order = ASKS_V1.load(deps.storage, ask_id.as_bytes())?;
+    let ask_price = book_price(&ask_order.price, contract_info.price_precision)?;
+
+    // bid prices are inverted in the book, so bids at or above the ask price sort first. scan one
+    // past the limit, so a match cut short by it can be reported
+    let mut candidates = BID_BOOK
+        .sub_prefix(&ask_order.quote)
+        .range(
+            deps.storage,
+            None,
+            Some(Bound::inclusive((u128::MAX - ask_price, u64::MAX))),
+            Order::Ascending,
+        )
+        .take(MAX_AUTO_MATCH_ITERATIONS + 1)
+        .map(|item| item.map(|(_, bid_id)| bid_id))
+        .collect::<StdResult<Vec<String>>>()?;
+    let limit_reached = candidates.len() > MAX_AUTO_MATCH_ITERATIONS;
+    candidates.truncate(MAX_AUTO_MATCH_ITERATIONS);
+
+    let mut response = Response::new();
+    for bid_id in candidates {
+        // stop once the ask is filled
+        let ask_order = match ASKS_V1.may_load(deps.storage, ask_id.as_bytes())? {
+            Some(ask_order) => ask_order,
+            None => break,
+        };
+        let bid_order = BIDS_V3.load(deps.storage, bid_id.as_bytes())?;
+
+        // skip self trades and stale bids
+        if bid_order.owner.eq(&ask_order.owner)
+            || order_expired(deps.storage, &env, &BID_POSITIONS, &bid_id)?
+        {
+            continue;
+        }
+
+        // round down to a size that fills to a whole quote amount, skip the bid if none does
+        let size = whole_fill_size(
+            ask_order.size.min(bid_order.get_remaining_base()),
+            &[bid_order.price.as_str()],
+        )?;
+        if size.is_zero() {
+            continue;
+        }
+
+        // resting bids fill at their own price
+        let fill = settle_match(
+            deps.branch(),
+            env.clone(),
+            ask_id.to_string(),
+            bid_id,
+            bid_order.price,
+            size,
+        )?;
+        response = response
+            .add_submessages(fill.messages)
+            .add_attributes(fill.attributes);
+    }
+
+    // crossing bids were left unscanned, so the ask may still be matchable
+    if limit_reached && ASKS_V1.may_load(deps.storage, ask_id.as_bytes())?.is_some() {
+        response = response.add_attribute(
+            "auto_match_limit_reached",
+            MAX_AUTO_MATCH_ITERATIONS.to_string(),
+        );
+    }
+
+    Ok(response)
+}
+
+// cross a new bid against resting asks, best price first and oldest first within a price
+fn match_incoming_bid(
+    mut deps: DepsMut,
+    env: Env,
+    bid_id: &str,
+) -> Result<Response, ContractError> {
+    let contract_info = get_contract_info(deps.storage)?;
+    let bid_order = BIDS_V3.load(deps.storage, bid_id.as_bytes())?;
+    let bid_price = book_price(&bid_order.price, contract_info.price_precision)?;
+
+    // scan one past the limit, so a match cut short by it can be reported
+    let mut candidates = ASK_BOOK
+        .sub_prefix(&bid_order.quote.denom)
+        .range(
+            deps.storage,
+            None,
+            Some(Bound::inclusive((bid_price, u64::MAX))),
+            Order::Ascending,
+        )
+        .take(MAX_AUTO_MATCH_ITERATIONS + 1)
+        .map(|item| item.map(|(_, ask_id)| ask_id))
+        .collect::<StdResult<Vec<String>>>()?;
+    let limit_reached = candidates.len() > MAX_AUTO_MATCH_ITERATIONS;
+    candidates.truncate(MAX_AUTO_MATCH_ITERATIONS);
+
+    let mut response = Response::new();
+    for ask_id in candidates {
+        // stop once the bid is filled
+        let bid_order = match BIDS_V3.may_load(deps.storage, bid_id.as_bytes())? {
+            Some(bid_order) => bid_order,
+            None => break,
+        };
+        let ask_order = ASKS_V1.load(deps.storage, ask_id.as_bytes())?;
+
+        // skip self trades, stale asks and convertible asks still awaiting issuer approval
+        if ask_order.owner.eq(&bid_order.owner) || !ask_matchable(deps.storage, &env, &ask_order)? {
+            continue;
+        }
+
+        // round down to a size that fills to a whole quote amount at both the ask and bid price,
+        // skip the ask if none does
+        let size = whole_fill_size(
+            ask_order.size.min(bid_order.get_remaining_base()),
+            &[ask_order.price.as_str(), bid_order.price.as_str()],
+        )?;
+        if size.is_zero() {
+            continue;
+        }
+
+        // resting asks fill at their own price, the bid is refunded the difference
+        let fill = settle_match(
+            deps.branch(),
+            env.clone(),
+            ask_id,
+            bid_id.to_string(),
+            ask_order.price,
+            size,
+        )?;
+        response = response
+            .add_submessages(fill.messages)
+            .add_attributes(fill.attributes);
+    }
+
+    // crossing asks were left unscanned, so the bid may still be matchable
+    if limit_reached && BIDS_V3.may_load(deps.storage, bid_id.as_bytes())?.is_some() {
+        response = response.add_attribute(
+            "auto_match_limit_reached",
+            MAX_AUTO_MATCH_ITERATIONS.to_string(),
+        );
+    }
+
+    Ok(response)
+}
+
+// whether an ask at a book price would cross a bid it could be matched with. like auto matching,
+// only the best bids up to the iteration limit are scanned
+fn crosses_resting_bid(
+    deps: Deps,
+    env: &Env,
+    ask_order: &AskOrderV1,
+    ask_price: u128,
+) -> Result<bool, ContractError> {
+    let candidates = BID_BOOK
+        .sub_prefix(&ask_order.quote)
+        .range(
+            deps.storage,
+            None,
+            Some(Bound::inclusive((u128::MAX - ask_price, u64::MAX))),
+            Order::Ascending,
+        )
+        .take(MAX_AUTO_MATCH_ITERATIONS)
+        .map(|item| item.map(|(_, bid_id)| bid_id))
+        .collect::<StdResult<Vec<String>>>()?;
+
+    for bid_id in candidates {
+        let bid_order = BIDS_V3.load(deps.storage, bid_id.as_bytes())?;
+        if bid_order.owner.ne(&ask_order.owner)
+            && !order_expired(deps.storage, env, &BID_POSITIONS, &bid_id)?
+        {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+// whether a bid at a book price would cross an ask it could be matched with. like auto matching,
+// only the best asks up to the iteration limit are scanned
+fn crosses_resting_ask(
+    deps: Deps,
+    env: &Env,
+    bid_order: &BidOrderV3,
+    bid_price: u128,
+) -> Result<bool, ContractError> {
+    let candidates = ASK_BOOK
+        .sub_prefix(&bid_order.quote.denom)
+        .range(
+            deps.storage,
+            None,
+            Some(Bound::inclusive((bid_price, u64::MAX))),
+            Order::Ascending,
+        )
+        .take(MAX_AUTO_MATCH_ITERATIONS)
+        .map(|item| item.map(|(_, ask_id)| ask_id))
+        .collect::<StdResult<Vec<String>>>()?;
+
+    for ask_id in candidates {
+        let ask_order = ASKS_V1.load(deps.storage, ask_id.as_bytes())?;
+        if ask_order.owner.ne(&bid_order.owner) && ask_matchable(deps.storage, env, &ask_order)? {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+// the rate bid fees are escrowed at, a scheduled bid may end up making or taking so escrows the
+// higher of its two rates
+fn bid_fee_escrow_rate(
+    deps: Deps,
+    env: &Env,
+    contract_info: &ContractInfoV3,
+    owner: &Addr,
+) -> Result<Decimal, ContractError> {
+    match FEE_SCHEDULE.may_load(deps.storage)? {
+        Some(fee_schedule) => Ok(scheduled_fee_rate(deps, env, &fee_schedule, owner, true)?
+            .max(scheduled_fee_rate(deps, env, &fee_schedule, owner, false)?)),
+        None => match &contract_info.bid_fee_info {
+            Some(bid_fee_info) => {
+                Decimal::from_str(&bid_fee_info.rate).map_err(|_| ContractError::InvalidFields {
+                    fields: vec![String::from("ContractInfo.bid_fee_info.rate")],
+                })
+            }
+            None => Ok(Decimal::from(0)),
+        },
+    }
+}
+
+// an account's scheduled rate for its side of a match, from its volume tier less any discount
+fn scheduled_fee_rate(
+    deps: Deps,
+    env: &Env,
+    fee_schedule: &FeeSchedule,
+    account: &Addr,
+    maker: bool,
+) -> Result<Decimal, ContractError> {
+    let volume = trailing_volume(deps.storage, env, account)?;
+
+    // tiers are stored by ascending minimum volume, so the last tier reached applies
+    let (maker_rate, taker_rate) = fee_schedule
+        .tiers
+        .iter()
+        .rev()
+        .find(|tier| volume.ge(&tier.min_volume))
+        .map_or(
+            (&fee_schedule.maker_rate, &fee_schedule.taker_rate),
+            |tier| (&tier.maker_rate, &tier.taker_rate),
+        );
+
+    let rate = Decimal::from_str(if maker { maker_rate } else { taker_rate }).map_err(|_| {
+        ContractError::InvalidFields {
+            fields: vec![String::from("fee_schedule")],
+        }
+    })?;
+
+    if fee_schedule.discounts.is_empty() {
+        return Ok(rate);
+    }
+
+    // the largest discount among the account's attributes applies
+    let querier = AttributeQuerier::new(&deps.querier);
+    let attributes_names: HashSet<String> = get_attributes(account.to_string(), &querier)?
+        .into_iter()
+        .map(|item| item.name)
+        .collect();
+    let mut discount = Decimal::zero();
+    for fee_discount in &fee_schedule.discounts {
+        if attributes_names.contains(&fee_discount.attribute) {
+            discount = discount.max(Decimal::from_str(&fee_discount.rate).map_err(|_| {
+                ContractError::InvalidFields {
+                    fields: vec![String::from("fee_schedule")],
+                }
+            })?);
+        }
+    }
+
+    rate.checked_mul(Decimal::ONE - discount)
+        .ok_or(ContractError::TotalOverflow)
+}
+
+// the first day still inside an account's trailing volume window
+fn volume_window_start(env: &Env) -> u64 {
+    (env.block.time.seconds() / SECONDS_PER_DAY + 1).saturating_sub(VOLUME_WINDOW_DAYS)
+}
+
+// an account's filled base over the trailing volume window
+fn trailing_volume(storage: &dyn Storage, env: &Env, account: &Addr) -> StdResult<Uint128> {
+    ACCOUNT_VOLUME
+        .prefix(account)
+        .range(
+            storage,
+            Some(Bound::inclusive(volume_window_start(env))),
+            None,
+            Order::Ascending,
+        )
+        .try_fold(Uint128::zero(), |total, item| {
+            item.map(|(_, volume)| total + volume)
+        })
+}
+
+// add filled base to an account's volume for the day, dropping days outside the window
+fn record_volume(
+    storage: &mut dyn Storage,
+    env: &Env,
+    account: &Addr,
+    size: Uint128,
+) -> StdResult<()> {
+    ACCOUNT_VOLUME.update(
+        storage,
+        (account, env.block.time.seconds() / SECONDS_PER_DAY),
+        |volume| -> StdResult<_> { Ok(volume.unwrap_or_default() + size) },
+    )?;
+
+    let stale_days = ACCOUNT_VOLUME
+        .prefix(account)
+        .keys(
+            storage,
+            None,
+            Some(Bound::exclusive(volume_window_start(env))),
+            Order::Ascending,
+        )
+        .collect::<StdResult<Vec<u64>>>()?;
+    for day in stale_days {
+        ACCOUNT_VOLUME.remove(storage, (account, day));
+    }
+
+    Ok(())
+}
+
+// convert an order price to an integer at the contract price precision, so the book sorts
+// numerically
+fn book_price(price: &str, price_precision: Uint128) -> Result<u128, ContractError> {
+    Decimal::from_str(price)
+        .ok()
+        .and_then(|price| {
+            price.checked_mul(Decimal::from(10u128.pow(price_precision.u128() as u32)))
+        })
+        .and_then(|price| price.to_u128())
+        .ok_or(ContractError::InvalidFields {
+            fields: vec![String::from("price")],
+        })
+}
+
+// round a fill size down to a multiple of the smallest size that is a whole quote amount at every
+// given price, settle_match rejects fills with a fractional total
+fn whole_fill_size(size: Uint128, prices: &[&str]) -> Result<Uint128, ContractError> {
+    let mut step = 1u128;
+    for price in prices {
+        let price = Decimal::from_str(price).map_err(|_| ContractError::InvalidFields {
+            fields: vec![String::from("price")],
+        })?;
+
+        // a price of mantissa / 10^scale is whole at multiples of 10^scale / gcd(mantissa, 10^scale)
+        let scale = 10u128.pow(price.scale());
+        let price_step = scale / gcd(price.mantissa().unsigned_abs(), scale);
+        step = step / gcd(step, price_step) * price_step;
+    }
+    Ok(size - Uint128::new(size.u128() % step))
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        let remainder = a % b;
+        a = b;
+        b = remainder;
+    }
+    a
+}
+
+// determine whether a good till time order has passed its expiry
+fn order_expired(
+    storage: &dyn Storage,
+    env: &Env,
+    positions: &Map<&[u8], BookPosition>,
+    id: &str,
+) -> StdResult<bool> {
+    Ok(positions
+        .may_load(storage, id.as_bytes())?
+        .and_then(|position| position.expires)
+        .map_or(false, |expires| expires <= env.block.time.seconds()))
+}
+
+// a resting ask can be matched unless it has expired or is a convertible ask still awaiting
+// issuer approval
+fn ask_matchable(storage: &dyn Storage, env: &Env, ask_order: &AskOrderV1) -> StdResult<bool> {
+    Ok(!order_expired(storage, env, &ASK_POSITIONS, &ask_order.id)?
+        && !matches!(
+            ask_order.class,
+            AskOrderClass::Convertible {
+                status: AskOrderStatus::PendingIssuerApproval
+            }
+        ))
+}
+
+// take the next order sequence, used for time priority within a price level
+fn next_order_sequence(storage: &mut dyn Storage) -> StdResult<u64> {
+    let sequence = ORDER_SEQUENCE.may_load(storage)?.unwrap_or_default() + 1;
+    ORDER_SEQUENCE.save(storage, &sequence)?;
+    Ok(sequence)
+}
+
+// add an ask to the book at its price level
+fn index_ask(
+    storage: &mut dyn Storage,
+    contract_info: &ContractInfoV3,
+    ask_order: &AskOrderV1,
+    expires: Option<u64>,
+) -> Result<(), ContractError> {
+    let position = BookPosition {
+        owner: ask_order.owner.to_owned(),
+        quote: ask_order.quote.to_owned(),
+        price: book_price(&ask_order.price, contract_info.price_precision)?,
+        sequence: next_order_sequence(storage)?,
+        expires,
+    };
+    ASK_BOOK.save(
+        storage,
+        (&position.quote, position.price, position.sequence),
+        &ask_order.id,
+    )?;
+    ASK_POSITIONS.save(storage, ask_order.id.as_bytes(), &position)?;
+    ASK_OWNERS.save(storage, (&ask_order.owner, &ask_order.id), &Empty {})?;
+    if let Some(expires) = expires {
+        ASK_EXPIRIES.save(storage, (expires, &ask_order.id), &Empty {})?;
+    }
+    Ok(())
+}
+
+// add a bid to the book at its price level, prices are inverted so the best bid sorts first
+fn index_bid(
+    storage: &mut dyn Storage,
+    contract_info: &ContractInfoV3,
+    bid_order: &BidOrderV3,
+    expires: Option<u64>,
+) -> Result<(), ContractError> {
+    let position = BookPosition {
+        owner: bid_order.owner.to_owned(),
+        quote: bid_order.quote.denom.to_owned(),
+        price: u128::MAX - book_price(&bid_order.price, contract_info.price_precision)?,
+        sequence: next_order_sequence(storage)?,
+        expires,
+    };
+    BID_BOOK.save(
+        storage,
+        (&position.quote, position.price, position.sequence),
+        &bid_order.id,
+    )?;
+    BID_POSITIONS.save(storage, bid_order.id.as_bytes(), &position)?;
+    BID_OWNERS.save(storage, (&bid_order.owner, &bid_order.id), &Empty {})?;
+    if let Some(expires) = expires {
+        BID_EXPIRIES.save(storage, (expires, &bid_order.id), &Empty {})?;
+    }
+    Ok(())
+}
+
+// remove an ask from the book, its owner index and its expiry index
+fn unindex_ask(storage: &mut dyn Storage, id: &str) -> StdResult<()> {
+    if let Some(position) = ASK_POSITIONS.may_load(storage, id.as_bytes())? {
+        ASK_BOOK.remove(
+            storage,
+            (&position.quote, position.price, position.sequence),
+        );
+        ASK_POSITIONS.remove(storage, id.as_bytes());
+        ASK_OWNERS.remove(storage, (&position.owner, id));
+        if let Some(expires) = position.expires {
+            ASK_EXPIRIES.remove(storage, (expires, id));
+        }
+    }
+    Ok(())
+}
+
+// remove a bid from the book, its owner index and its expiry index
+fn unindex_bid(storage: &mut dyn Storage, id: &str) -> StdResult<()> {
+    if let Some(position) = BID_POSITIONS.may_load(storage, id.as_bytes())? {
+        BID_BOOK.remove(
+            storage,
+            (&position.quote, position.price, position.sequence),
+        );
+        BID_POSITIONS.remove(storage, id.as_bytes());
+        BID_OWNERS.remove(storage, (&position.owner, id));
+        if let Some(expires) = position.expires {
+            BID_EXPIRIES.remove(storage, (expires, id));
+        }
+    }
+    Ok(())
+}
+
+// add resting orders created before the book was indexed
+fn index_existing_orders(deps: DepsMut) -> Result<(), ContractError> {
+    let contract_info = get_contract_info(deps.storage)?;
+
+    let ask_orders = ASKS_V1
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, ask_order)| ask_order))
+        .collect::<StdResult<Vec<AskOrderV1>>>()?;
+    for ask_order in ask_orders {
+        if ASK_POSITIONS
+            .may_load(deps.storage, ask_order.id.as_bytes())?
+            .is_none()
+        {
+            index_ask(deps.storage, &contract_info, &ask_order, None)?;
+        }
+    }
+
+    let bid_orders = BIDS_V3
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, bid_order)| bid_order))
+        .collect::<StdResult<Vec<BidOrderV3>>>()?;
+    for bid_order in bid_orders {
+        if BID_POSITIONS
+            .may_load(deps.storage, bid_order.id.as_bytes())?
+            .is_none()
+        {
+            index_bid(deps.storage, &contract_info, &bid_order, None)?;
+        }
+    }
+
+    Ok(())
+}
+
+// smart contract migrate/upgrade entrypoint
+#[entry_point]
+pub fn migrate(mut deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    msg.validate()?;
+
+    // build response
+    let mut response: Response = Response::new();
+
+    // migrate contract_info
+    migrate_contract_info(deps.branch(), &msg)?;
+
+    // migrate ask orders
+    migrate_ask_orders(deps.branch(), &msg)?;
+
+    // migrate bid orders
+    response = migrate_bid_orders(deps.branch(), env, &msg, response)?;
+
+    // index resting orders into the book
+    index_existing_orders(deps.branch())?;
+
+    // lastly, migrate version_info
+    migrate_version_info(deps.branch())?;
+
+    Ok(response)
+}
+
+// smart contract query entrypoint
+#[entry_point]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    msg.validate()?;
+
+    match msg {
+        QueryMsg::GetAsk { id } => {
+            return to_binary(&ASKS_V1.load(deps.storage, id.as_bytes())?);
+        }
+        QueryMsg::GetBid { id } => {
+            return to_binary(&BIDS_V3.load(deps.storage, id.as_bytes())?);
+        }
+        QueryMsg::GetContractInfo {} => to_binary(&get_contract_info(deps.storage)?),
+        QueryMsg::GetVersionInfo {} => to_binary(&get_version_info(deps.storage)?),
+        QueryMsg::GetBestBidAsk { quote } => to_binary(&query_best_bid_ask(deps, env, quote)?),
+        QueryMsg::GetBookDepth { quote, levels } => {
+            to_binary(&query_book_depth(deps, env, quote, levels)?)
+        }
+        QueryMsg::GetOrdersByOwner {
+            owner,
+            side,
+            start_after,
+            limit,
+        } => query_orders_by_owner(deps, owner, side, start_after, limit),
+        QueryMsg::GetFeeSchedule {} => to_binary(&FEE_SCHEDULE.may_load(deps.storage)?),
+        QueryMsg::GetFeeRates { account } => to_binary(&query_fee_rates(deps, env, account)?),
+    }
+}
+
+// get the best priced, then oldest, ask and bid for a quote denom that could be matched now
+fn query_best_bid_ask(deps: Deps, env: Env, quote: String) -> StdResult<BestBidAsk> {
+    let mut best_ask = None;
+    for item in ASK_BOOK
+        .sub_prefix(&quote)
+        .range(deps.storage, None, None, Order::Ascending)
+    {
+        let (_, id) = item?;
+        let ask_order = ASKS_V1.load(deps.storage, id.as_bytes())?;
+        if ask_matchable(deps.storage, &env, &ask_order)? {
+            best_ask = Some(ask_order);
+            break;
+        }
+    }
+
+    let mut best_bid = None;
+    for item in BID_BOOK
+        .sub_prefix(&quote)
+        .range(deps.storage, None, None, Order::Ascending)
+    {
+        let (_, id) = item?;
+        if !order_expired(deps.storage, &env, &BID_POSITIONS, &id)? {
+            best_bid = Some(BIDS_V3.load(deps.storage, id.as_bytes())?);
+            break;
+        }
+    }
+
+    Ok(BestBidAsk { best_ask, best_bid })
+}
+
+// aggregate the open size at each of the best price levels on both sides of the book, leaving out
+// orders that could not be matched now
+fn query_book_depth(deps: Deps, env: Env, quote: String, levels: u32) -> StdResult<BookDepth> {
+    let contract_info = get_contract_info(deps.storage)?;
+    let levels = levels.min(MAX_BOOK_DEPTH_LEVELS) as usize;
+
+    let mut asks: Vec<PriceLevel> = Vec::new();
+    let mut last_price: Option<u128> = None;
+    for item in ASK_BOOK
+        .sub_prefix(&quote)
+        .range(deps.storage, None, None, Order::Ascending)
+    {
+        let ((price, _), id) = item?;
+        let ask_order = ASKS_V1.load(deps.storage, id.as_bytes())?;
+        if !ask_matchable(deps.storage, &env, &ask_order)? {
+            continue;
+        }
+        if last_price.ne(&Some(price)) {
+            if asks.len() == levels {
+                break;
+            }
+            asks.push(PriceLevel {
+                price: level_price(price, contract_info.price_precision),
+                size: Uint128::zero(),
+                orders: 0,
+            });
+            last_price = Some(price);
+        }
+        if let Some(level) = asks.last_mut() {
+            level.size += ask_order.size;
+            level.orders += 1;
+        }
+    }
+
+    let mut bids: Vec<PriceLevel> = Vec::new();
+    let mut last_price: Option<u128> = None;
+    for item in BID_BOOK
+        .sub_prefix(&quote)
+        .range(deps.storage, None, None, Order::Ascending)
+    {
+        let ((price, _), id) = item?;
+        if order_expired(deps.storage, &env, &BID_POSITIONS, &id)? {
+            continue;
+        }
+        if last_price.ne(&Some(price)) {
+            if bids.len() == levels {
+                break;
+            }
+            bids.push(PriceLevel {
+                price: level_price(u128::MAX - price, contract_info.price_precision),
+                size: Uint128::zero(),
+                orders: 0,
+            });
+            last_price = Some(price);
+        }
+        if let Some(level) = bids.last_mut() {
+            level.size += BIDS_V3
+                .load(deps.storage, id.as_bytes())?
+                .get_remaining_base();
+            level.orders += 1;
+        }
+    }
+
+    Ok(BookDepth { asks, bids })
+}
+
+// convert a book price back to a decimal string
+fn level_price(price: u128, price_precision: Uint128) -> String {
+    Decimal::from_i128_with_scale(price as i128, price_precision.u128() as u32)
+        .normalize()
+        .to_string()
+}
+
+// list an owner's open asks or bids, ordered by id
+fn query_orders_by_owner(
+    deps: Deps,
+    owner: String,
+    side: OrderSide,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Binary> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    match side {
+        OrderSide::Ask => {
+            let asks = ASK_OWNERS
+                .prefix(&owner)
+                .keys(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .map(|id| ASKS_V1.load(deps.storage, id?.as_bytes()))
+                .collect::<StdResult<Vec<AskOrderV1>>>()?;
+            to_binary(&asks)
+        }
+        OrderSide::Bid => {
+            let bids = BID_OWNERS
+                .prefix(&owner)
+                .keys(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .map(|id| BIDS_V3.load(deps.storage, id?.as_bytes()))
+                .collect::<StdResult<Vec<BidOrderV3>>>()?;
+            to_binary(&bids)
+        }
+    }
+}
+
+// get an account's trailing volume and the fee rates it currently pays
+fn query_fee_rates(deps: Deps, env: Env, account: String) -> StdResult<FeeRates> {
+    let account = deps.api.addr_validate(&account)?;
+    let contract_info = get_contract_info(deps.storage)?;
+    let fee_schedule = FEE_SCHEDULE.may_load(deps.storage)?;
+
+    let rates = || -> Result<FeeRates, ContractError> {
+        let (maker_rate, taker_rate) = match &fee_schedule {
+            Some(fee_schedule) => (
+                Some(scheduled_fee_rate(deps, &env, fee_schedule, &account, true)?.to_string()),
+                Some(scheduled_fee_rate(deps, &env, fee_schedule, &account, false)?.to_string()),
+            ),
+            None => (None, None),
+        };
+
+        Ok(FeeRates {
+            volume: trailing_volume(deps.storage, &env, &account)?,
+            maker_rate,
+            taker_rate,
+            bid_fee_escrow_rate: bid_fee_escrow_rate(deps, &env, &contract_info, &account)?
+                .to_string(),
+        })
+    };
+
+    rates().map_err(|error| StdError::generic_err(error.to_string()))
+}
+
+// unit tests
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{mock_env, mock_info, MockQuerier};
+    use cosmwasm_std::{
+        Addr, BankMsg, ContractInfoResponse, ContractResult, Storage, SubMsg, SystemError,
+        SystemResult, Uint128, WasmQuery,
+    };
+
+    use super::*;
+    use crate::fee_schedule::{FeeDiscount, FeeTier};
+    use provwasm_mocks::mock_provenance_dependencies;
+    use provwasm_std::types::provenance::attribute::v1::{
+        Attribute, AttributeType, QueryAttributesRequest, QueryAttributesResponse,
+    };
+
+    #[test]
+    fn query_contract_info() {
+        // setup
+        let mut deps = mock_provenance_dependencies();
+        setup_test_base(
+            &mut deps.storage,
+            &ContractInfoV3 {
+                name: "contract_name".into(),
+                bind_name: "".into(),
+                base_denom: "base_denom".into(),
+                convertible_base_denoms: vec!["con_base_1".into(), "con_base_2".into()],
+                supported_quote_denoms: vec!["quote_1".into(), "quote_2".into()],
+                approvers: vec![Addr::unchecked("exec_1"), Addr::unchecked("exec_2")],
+                executors: vec![Addr::unchecked("exec_1"), Addr::unchecked("exec_2")],
+                ask_fee_info: None,
+                bid_fee_info: None,
+                ask_required_attributes: vec!["ask_tag_1".into(), "ask_tag_2".into()],
+                bid_required_attributes: vec!["bid_tag_1".into(), "bid_tag_2".into()],
+                price_precision: Uint128::new(2),
+                size_increment: Uint128::new(100),
+            },
+        );
+
+        // query for contract_info
+        let query_contract_info_response =
+            query(deps.as_ref(), mock_env(), QueryMsg::GetContractInfo {});
+
+        match query_contract_info_response {
+            Ok(contract_info) => {
+                assert_eq!(
+                    contract_info,
+                    to_binary(&get_contract_info(&deps.storage).unwrap()).unwrap()
+                )
+            }
+            Err(error) => panic!("unexpected error: {:?}", error),
+        }
+    }
+
+    #[test]
+    fn create_bid_auto_match_fills_best_ask_first() {
+        // setup
+        let mut deps = mock_provenance_dependencies();
+        setup_test_base(
+            &mut deps.storage,
+            &ContractInfoV3 {
+                name: "contract_name".into(),
+                bind_name: "".into(),
+                base_denom: "base_1".into(),
+                convertible_base_denoms: vec![],
+                supported_quote_denoms: vec!["quote_1".into()],
+                approvers: vec![],
+                executors: vec![Addr::unchecked("exec_1")],
+                ask_fee_info: None,
+                bid_fee_info: None,
+                ask_required_attributes: vec![],
+                bid_required_attributes: vec![],
+                price_precision: Uint128::new(2),
+                size_increment: Uint128::new(100),
+            },
+        );
+
+        // create a resting ask at 3 and a cheaper one at 2
+        for (id, price) in [("ask_1", "3"), ("ask_2", "2")] {
+            if let Err(error) = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("asker", &coins(100, "base_1")),
+                ExecuteMsg::CreateAsk {
+                    id: id.into(),
+                    base: "base_1".into(),
+                    quote: "quote_1".into(),
+                    price: price.into(),
+                    size: Uint128::new(100),
+                    auto_match: None,
+                    time_in_force: None,
+                },
+            ) {
+                panic!("unexpected error: {:?}", error)
+            }
+        }
+
+        // create a crossing bid at 3 with auto match
+        let create_bid_response = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bidder", &coins(300, "quote_1")),
+            ExecuteMsg::CreateBid {
+                id: "bid_1".into(),
+                base: "base_1".into(),
+                fee: None,
+                price: "3".into(),
+                quote: "quote_1".into(),
+                quote_size: Uint128::new(300),
+                size: Uint128::new(100),
+                auto_match: Some(true),
+                time_in_force: None,
+            },
+        );
+
+        // the bid fills against the cheaper ask at its price and the difference is refunded
+        match create_bid_response {
+            Ok(response) => {
+                assert_eq!(
+                    response.messages,
+                    vec![
+                        SubMsg::new(BankMsg::Send {
+                            to_address: "asker".into(),
+                            amount: coins(200, "quote_1"),
+                        }),
+                        SubMsg::new(BankMsg::Send {
+                            to_address: "bidder".into(),
+                            amount: coins(100, "base_1"),
+                        }),
+                        SubMsg::new(BankMsg::Send {
+                            to_address: "bidder".into(),
+                            amount: coins(100, "quote_1"),
+                        }),
+                    ]
+                );
+            }
+            Err(error) => panic!("unexpected error: {:?}", error),
+        }
+
+        // the filled orders are removed from storage and the book
+        assert!(ASKS_V1.may_load(&deps.storage, b"ask_2").unwrap().is_none());
+        assert!(BIDS_V3.may_load(&deps.storage, b"bid_1").unwrap().is_none());
+        assert!(ASK_POSITIONS
+            .may_load(&deps.storage, b"ask_2")
+            .unwrap()
+            .is_none());
+        assert!(BID_POSITIONS
+            .may_load(&deps.storage, b"bid_1")
+            .unwrap()
+            .is_none());
+
+        // the worse priced ask keeps resting
+        assert!(ASKS_V1.may_load(&deps.storage, b"ask_1").unwrap().is_some());
+        assert!(ASK_POSITIONS
+            .may_load(&deps.storage, b"ask_1")
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn auto_match_skips_self_trades_and_stale_orders() {
+        // setup
+        let mut deps = mock_provenance_dependencies();
+        setup_test_base(
+            &mut deps.storage,
+            &ContractInfoV3 {
+                name: "contract_name".into(),
+                bind_name: "".into(),
+                base_denom: "base_1".into(),
+                convertible_base_denoms: vec![],
+                supported_quote_denoms: vec!["quote_1".into()],
+                approvers: vec![],
+                executors: vec![Addr::unchecked("exec_1")],
+                ask_fee_info: None,
+                bid_fee_info: None,
+                ask_required_attributes: vec![],
+                bid_required_attributes: vec![],
+                price_precision: Uint128::new(2),
+                size_increment: Uint128::new(100),
+            },
+        );
+
+        // the bidder's own ask at 1, an ask at 2 good for a minute and an ask at 3
+        let expires = mock_env().block.time.plus_seconds(60);
+        for (owner, id, price, time_in_force) in [
+            ("bidder", "ask_self", "1", None),
+            (
+                "asker",
+                "ask_stale",
+                "2",
+                Some(TimeInForce::GoodTillTime { expires }),
+            ),
+            ("asker", "ask_live", "3", None),
+        ] {
+            if let Err(error) = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(owner, &coins(100, "base_1")),
+                ExecuteMsg::CreateAsk {
+                    id: id.into(),
+                    base: "base_1".into(),
+                    quote: "quote_1".into(),
+                    price: price.into(),
+                    size: Uint128::new(100),
+                    auto_match: None,
+                    time_in_force,
+                },
+            ) {
+                panic!("unexpected error: {:?}", error)
+            }
+        }
+
+        // once the ask at 2 has expired, a crossing bid skips it and the bidder's own ask
+        let mut env = mock_env();
+        env.block.time = expires;
+        let create_bid_response = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("bidder", &coins(300, "quote_1")),
+            ExecuteMsg::CreateBid {
+                id: "bid_1".into(),
+                base: "base_1".into(),
+                fee: None,
+                price: "3".into(),
+                quote: "quote_1".into(),
+                quote_size: Uint128::new(300),
+                size: Uint128::new(100),
+                auto_match: Some(true),
+                time_in_force: None,
+            },
+        );
+        match create_bid_response {
+            Ok(response) => {
+                assert_eq!(
+                    response.messages,
+                    vec![
+                        SubMsg::new(BankMsg::Send {
+                            to_address: "asker".into(),
+                            amount: coins(300, "quote_1"),
+                        }),
+                        SubMsg::new(BankMsg::Send {
+                            to_address: "bidder".into(),
+                            amount: coins(100, "base_1"),
+                        }),
+                    ]
+                );
+                assert!(!response
+                    .attributes
+                    .iter()
+                    .any(|attribute| attribute.key == "auto_match_limit_reached"));
+            }
+            Err(error) => panic!("unexpected error: {:?}", error),
+        }
+        assert!(ASKS_V1
+            .may_load(&deps.storage, b"ask_live")
+            .unwrap()
+            .is_none());
+        assert!(ASKS_V1
+            .may_load(&deps.storage, b"ask_self")
+            .unwrap()
+            .is_some());
+        assert!(ASKS_V1
+            .may_load(&deps.storage, b"ask_stale")
+            .unwrap()
+            .is_some());
+
+        // fill the rest of the scan with the bidder's own asks, ahead of a fresh ask at 2
+        for index in 1..MAX_AUTO_MATCH_ITERATIONS {
+            if let Err(error) = execute(
+                deps.as_mut(),
+                env.clone(),
+                mock_info("bidder", &coins(100, "base_1")),
+                ExecuteMsg::CreateAsk {
+                    id: format!("ask_self_{}", index),
+                    base: "base_1".into(),
+                    quote: "quote_1".into(),
+                    price: "1".into(),
+                    size: Uint128::new(100),
+                    auto_match: None,
+                    time_in_force: None,
+                },
+            ) {
+                panic!("unexpected error: {:?}", error)
+            }
+        }
+        if let Err(error) = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("asker", &coins(100, "base_1")),
+            ExecuteMsg::CreateAsk {
+                id: "ask_live_2".into(),
+                base: "base_1".into(),
+                quote: "quote_1".into(),
+                price: "2".into(),
+                size: Uint128::new(100),
+                auto_match: None,
+                time_in_force: None,
+            },
+        ) {
+            panic!("unexpected error: {:?}", error)
+        }
+
+        // the scan stops at the limit before reaching the fresh ask, and says so
+        let create_bid_response = execute(
+            deps.as_mut(),
+            env,
+            mock_info("bidder", &coins(200, "quote_1")),
+            ExecuteMsg::CreateBid {
+                id: "bid_2".into(),
+                base: "base_1".into(),
+                fee: None,
+                price: "2".into(),
+                quote: "quote_1".into(),
+                quote_size: Uint128::new(200),
+                size: Uint128::new(100),
+                auto_match: Some(true),
+                time_in_force: None,
+            },
+        );
+        match create_bid_response {
+            Ok(response) => {
+                assert!(response.messages.is_empty());
+                assert!(response.attributes.contains(&attr(
+                    "auto_match_limit_reached",
+                    MAX_AUTO_MATCH_ITERATIONS.to_string()
+                )));
+            }
+            Err(error) => panic!("unexpected error: {:?}", error),
+        }
+        assert!(BIDS_V3.may_load(&deps.storage, b"bid_2").unwrap().is_some());
+        assert!(ASKS_V1
+            .may_load(&deps.storage, b"ask_live_2")
+            .unwrap()
+            .is_some());
     }
 
-    // is base a restricted marker
-    let is_base_restricted_marker = is_restricted_marker(&deps.querier, ask_order.base.clone());
+    #[test]
+    fn auto_match_rounds_fills_to_whole_quote_amounts() {
+        // setup
+        let mut deps = mock_provenance_dependencies();
+        setup_test_base(
+            &mut deps.storage,
+            &ContractInfoV3 {
+                name: "contract_name".into(),
+                bind_name: "".into(),
+                base_denom: "base_1".into(),
+                convertible_base_denoms: vec![],
+                supported_quote_denoms: vec!["quote_1".into()],
+                approvers: vec![],
+                executors: vec![Addr::unchecked("exec_1")],
+                ask_fee_info: None,
+                bid_fee_info: None,
+                ask_required_attributes: vec![],
+                bid_required_attributes: vec![],
+                price_precision: Uint128::new(2),
+                size_increment: Uint128::new(1),
+            },
+        );
 
-    // is quote a restricted marker
-    let is_quote_restricted_marker =
-        is_restricted_marker(&deps.querier, bid_order.quote.denom.clone());
+        // a resting ask of 4 at 0.25, which only fills to a whole quote amount in multiples of 4
+        if let Err(error) = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("asker", &coins(4, "base_1")),
+            ExecuteMsg::CreateAsk {
+                id: "ask_1".into(),
+                base: "base_1".into(),
+                quote: "quote_1".into(),
+                price: "0.25".into(),
+                size: Uint128::new(4),
+                auto_match: None,
+                time_in_force: None,
+            },
+        ) {
+            panic!("unexpected error: {:?}", error)
+        }
 
-    let mut response = Response::new();
-    response = response.add_attributes(vec![
-        attr("action", ContractAction::Execute.to_string()),
-        attr("ask_id", &ask_id),
-        attr("bid_id", &bid_id),
-        attr("base", &bid_order.base.denom),
-        attr("quote", &ask_order.quote),
-        attr("price", &execute_price.to_string()),
-        attr("size", &execute_size.to_string()),
-    ]);
+        // a crossing bid of 2 would fill for 0.5, so it skips the ask and rests
+        let create_bid_response = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bidder", &coins(1, "quote_1")),
+            ExecuteMsg::CreateBid {
+                id: "bid_1".into(),
+                base: "base_1".into(),
+                fee: None,
+                price: "0.5".into(),
+                quote: "quote_1".into(),
+                quote_size: Uint128::new(1),
+                size: Uint128::new(2),
+                auto_match: Some(true),
+                time_in_force: None,
+            },
+        );
+        match create_bid_response {
+            Ok(response) => assert!(response.messages.is_empty()),
+            Err(error) => panic!("unexpected error: {:?}", error),
+        }
+        assert_eq!(
+            ASKS_V1.load(&deps.storage, b"ask_1").unwrap().size,
+            Uint128::new(4)
+        );
+        assert!(BIDS_V3.may_load(&deps.storage, b"bid_1").unwrap().is_some());
+
+        // a crossing bid of 6 fills 4 and rests with the rest
+        let create_bid_response = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bidder", &coins(3, "quote_1")),
+            ExecuteMsg::CreateBid {
+                id: "bid_2".into(),
+                base: "base_1".into(),
+                fee: None,
+                price: "0.5".into(),
+                quote: "quote_1".into(),
+                quote_size: Uint128::new(3),
+                size: Uint128::new(6),
+                auto_match: Some(true),
+                time_in_force: None,
+            },
+        );
+        match create_bid_response {
+            Ok(response) => {
+                assert_eq!(
+                    response.messages,
+                    vec![
+                        SubMsg::new(BankMsg::Send {
+                            to_address: "asker".into(),
+                            amount: coins(1, "quote_1"),
+                        }),
+                        SubMsg::new(BankMsg::Send {
+                            to_address: "bidder".into(),
+                            amount: coins(4, "base_1"),
+                        }),
+                        SubMsg::new(BankMsg::Send {
+                            to_address: "bidder".into(),
+                            amount: coins(1, "quote_1"),
+                        }),
+                    ]
+                );
+            }
+            Err(error) => panic!("unexpected error: {:?}", error),
+        }
+        assert!(ASKS_V1.may_load(&deps.storage, b"ask_1").unwrap().is_none());
+        assert_eq!(
+            BIDS_V3
+                .load(&deps.storage, b"bid_2")
+                .unwrap()
+                .get_remaining_base(),
+            Uint128::new(2)
+        );
+    }
 
-    // calculate ask fees and create message if applicable
-    let ask_fee = match contract_info.ask_fee_info {
-        // calculate ask fee using total
-        Some(ask_fee_info) => {
-            match Decimal::from_str(&ask_fee_info.rate)
-                .map_err(|_| ContractError::InvalidFields {
-                    fields: vec![String::from("ContractInfo.ask_fee_info.rate")],
-                })?
-                .checked_mul(actual_gross_proceeds)
-                .ok_or(ContractError::TotalOverflow)?
-                .round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero)
-                .to_u128()
-                .ok_or(ContractError::TotalOverflow)?
-            {
-                0u128 => None,
-                fee_total => {
-                    let ask_fee = Coin {
-                        denom: bid_order.quote.denom.to_owned(),
-                        amount: Uint128::new(fee_total),
-                    };
+    #[test]
+    fn query_book_depth_and_orders_by_owner() {
+        // setup
+        let mut deps = mock_provenance_dependencies();
+        setup_test_base(
+            &mut deps.storage,
+            &ContractInfoV3 {
+                name: "contract_name".into(),
+                bind_name: "".into(),
+                base_denom: "base_1".into(),
+                convertible_base_denoms: vec!["con_base_1".into()],
+                supported_quote_denoms: vec!["quote_1".into()],
+                approvers: vec![],
+                executors: vec![Addr::unchecked("exec_1")],
+                ask_fee_info: None,
+                bid_fee_info: None,
+                ask_required_attributes: vec![],
+                bid_required_attributes: vec![],
+                price_precision: Uint128::new(2),
+                size_increment: Uint128::new(100),
+            },
+        );
 
-                    response = add_transfer(
-                        response,
-                        is_quote_restricted_marker.to_owned(),
-                        fee_total,
-                        bid_order.quote.denom.to_owned(),
-                        ask_fee_info.account,
-                        env.contract.address.to_owned(),
-                        env.contract.address.to_owned(),
-                    );
+        // create two asks at 2, one at 3 and a bid at 1
+        for (id, price) in [("ask_1", "2"), ("ask_2", "2.00"), ("ask_3", "3")] {
+            if let Err(error) = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("asker", &coins(100, "base_1")),
+                ExecuteMsg::CreateAsk {
+                    id: id.into(),
+                    base: "base_1".into(),
+                    quote: "quote_1".into(),
+                    price: price.into(),
+                    size: Uint128::new(100),
+                    auto_match: None,
+                    time_in_force: None,
+                },
+            ) {
+                panic!("unexpected error: {:?}", error)
+            }
+        }
 
-                    // subtract the fees and assign to net proceeds
-                    net_proceeds =
-                        net_proceeds
-                            .checked_sub(Uint128::new(fee_total))
-                            .map_err(|error| {
-                                ContractError::Std(StdError::Overflow { source: error })
-                            })?;
+        if let Err(error) = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bidder", &coins(100, "quote_1")),
+            ExecuteMsg::CreateBid {
+                id: "bid_1".into(),
+                base: "base_1".into(),
+                fee: None,
+                price: "1".into(),
+                quote: "quote_1".into(),
+                quote_size: Uint128::new(100),
+                size: Uint128::new(100),
+                auto_match: None,
+                time_in_force: None,
+            },
+        ) {
+            panic!("unexpected error: {:?}", error)
+        }
 
-                    Some(ask_fee)
-                }
-            }
+        // a convertible ask awaiting approval at 1, and an ask and a bid good for a minute at 1.5
+        let expires = mock_env().block.time.plus_seconds(60);
+        if let Err(error) = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("asker", &coins(100, "con_base_1")),
+            ExecuteMsg::CreateAsk {
+                id: "ask_convertible".into(),
+                base: "con_base_1".into(),
+                quote: "quote_1".into(),
+                price: "1".into(),
+                size: Uint128::new(100),
+                auto_match: None,
+                time_in_force: None,
+            },
+        ) {
+            panic!("unexpected error: {:?}", error)
         }
-        None => None,
-    };
 
-    response = response.add_attribute(
-        "ask_fee",
-        match ask_fee {
-            None => Uint128::zero(),
-            Some(fee) => fee.amount,
-        },
-    );
+        if let Err(error) = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("asker", &coins(100, "base_1")),
+            ExecuteMsg::CreateAsk {
+                id: "ask_stale".into(),
+                base: "base_1".into(),
+                quote: "quote_1".into(),
+                price: "1.5".into(),
+                size: Uint128::new(100),
+                auto_match: None,
+                time_in_force: Some(TimeInForce::GoodTillTime { expires }),
+            },
+        ) {
+            panic!("unexpected error: {:?}", error)
+        }
 
-    // get bid fees and create message if applicable
-    let actual_bid_fee = match &bid_order.fee {
-        Some(_) => bid_order.calculate_fee(Uint128::new(
-            actual_gross_proceeds
-                .to_u128()
-                .ok_or(ContractError::TotalOverflow)?,
-        ))?,
-        None => None,
-    };
+        if let Err(error) = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bidder", &coins(150, "quote_1")),
+            ExecuteMsg::CreateBid {
+                id: "bid_stale".into(),
+                base: "base_1".into(),
+                fee: None,
+                price: "1.5".into(),
+                quote: "quote_1".into(),
+                quote_size: Uint128::new(150),
+                size: Uint128::new(100),
+                auto_match: None,
+                time_in_force: Some(TimeInForce::GoodTillTime { expires }),
+            },
+        ) {
+            panic!("unexpected error: {:?}", error)
+        }
 
-    // add bid fee message
-    match &actual_bid_fee {
-        Some(bid_fee) => match contract_info.bid_fee_info {
-            Some(bid_fee_info) => {
-                response = add_transfer(
-                    response,
-                    is_quote_restricted_marker.to_owned(),
-                    bid_fee.amount.to_owned().u128(),
-                    bid_fee.denom.to_owned(),
-                    bid_fee_info.account,
-                    env.contract.address.to_owned(),
-                    env.contract.address.to_owned(),
-                );
-            }
-            None => return Err(ContractError::BidFeeAccountMissing),
-        },
-        None => (),
+        // once expired, unswept orders and the pending convertible ask are left out
+        let mut env = mock_env();
+        env.block.time = expires;
+
+        // the best ask is the oldest at the lowest price
+        let best_bid_ask =
+            query_best_bid_ask(deps.as_ref(), env.clone(), "quote_1".into()).unwrap();
+        assert_eq!(best_bid_ask.best_ask.unwrap().id, "ask_1");
+        assert_eq!(best_bid_ask.best_bid.unwrap().id, "bid_1");
+
+        // the top level aggregates both asks at 2
+        let book_depth = query_book_depth(deps.as_ref(), env, "quote_1".into(), 1).unwrap();
+        assert_eq!(book_depth.asks.len(), 1);
+        assert_eq!(book_depth.asks[0].price, "2");
+        assert_eq!(book_depth.asks[0].size, Uint128::new(200));
+        assert_eq!(book_depth.asks[0].orders, 2);
+        assert_eq!(book_depth.bids.len(), 1);
+        assert_eq!(book_depth.bids[0].price, "1");
+        assert_eq!(book_depth.bids[0].size, Uint128::new(100));
+
+        // page through the asker's orders
+        let orders_response = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GetOrdersByOwner {
+                owner: "asker".into(),
+                side: OrderSide::Ask,
+                start_after: Some("ask_1".into()),
+                limit: Some(1),
+            },
+        )
+        .unwrap();
+        let orders: Vec<AskOrderV1> = cosmwasm_std::from_binary(&orders_response).unwrap();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].id, "ask_2");
     }
 
-    response = response.add_attribute(
-        "bid_fee",
-        match &actual_bid_fee {
-            None => Uint128::zero(),
-            Some(fee) => fee.amount,
-        },
-    );
+    #[test]
+    fn sweep_expired_refunds_good_till_time_ask() {
+        // setup
+        let mut deps = mock_provenance_dependencies();
+        setup_test_base(
+            &mut deps.storage,
+            &ContractInfoV3 {
+                name: "contract_name".into(),
+                bind_name: "".into(),
+                base_denom: "base_1".into(),
+                convertible_base_denoms: vec![],
+                supported_quote_denoms: vec!["quote_1".into()],
+                approvers: vec![],
+                executors: vec![Addr::unchecked("exec_1")],
+                ask_fee_info: None,
+                bid_fee_info: None,
+                ask_required_attributes: vec![],
+                bid_required_attributes: vec![],
+                price_precision: Uint128::new(2),
+                size_increment: Uint128::new(100),
+            },
+        );
 
-    // add 'send quote to asker' and 'send base to bidder' messages
-    match &ask_order.class {
-        AskOrderClass::Basic => {
-            response = add_transfer(
-                response,
-                is_quote_restricted_marker.to_owned(),
-                net_proceeds.into(),
-                bid_order.quote.denom.to_owned(),
-                ask_order.owner.to_owned(),
-                env.contract.address.to_owned(),
-                env.contract.address.to_owned(),
-            );
-            response = add_transfer(
-                response,
-                is_base_restricted_marker.to_owned(),
-                execute_size.into(),
-                ask_order.base.to_owned(),
-                bid_order.owner.to_owned(),
-                env.contract.address.to_owned(),
-                env.contract.address.to_owned(),
-            );
+        // immediate orders must be matched on creation
+        let create_ask_response = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("asker", &coins(100, "base_1")),
+            ExecuteMsg::CreateAsk {
+                id: "ask_1".into(),
+                base: "base_1".into(),
+                quote: "quote_1".into(),
+                price: "2".into(),
+                size: Uint128::new(100),
+                auto_match: None,
+                time_in_force: Some(TimeInForce::ImmediateOrCancel),
+            },
+        );
+        match create_ask_response {
+            Err(ContractError::TimeInForceRequiresAutoMatch) => (),
+            result => panic!("unexpected result: {:?}", result),
         }
-        AskOrderClass::Convertible {
-            status:
-                AskOrderStatus::Ready {
-                    approver,
-                    converted_base,
-                },
-        } => {
-            response = add_transfer(
-                response,
-                is_base_restricted_marker.to_owned(),
-                execute_size.into(),
-                converted_base.to_owned().denom,
-                bid_order.owner.to_owned(),
-                env.contract.address.to_owned(),
-                env.contract.address.to_owned(),
-            );
-            response = add_transfer(
-                response,
-                is_base_restricted_marker,
-                execute_size.into(),
-                ask_order.base.to_owned(),
-                approver.to_owned(),
-                env.contract.address.to_owned(),
-                env.contract.address.to_owned(),
-            );
 
-            response = add_transfer(
-                response,
-                is_quote_restricted_marker.to_owned(),
-                net_proceeds.into(),
-                bid_order.quote.denom.clone(),
-                approver.to_owned(),
-                env.contract.address.to_owned(),
-                env.contract.address.to_owned(),
-            );
-        }
-        AskOrderClass::Convertible { status } => {
-            return Err(ContractError::AskOrderNotReady {
-                current_status: format!("{:?}", status),
-            });
+        // create an ask good for an hour
+        let expires = mock_env().block.time.plus_seconds(3600);
+        if let Err(error) = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("asker", &coins(100, "base_1")),
+            ExecuteMsg::CreateAsk {
+                id: "ask_1".into(),
+                base: "base_1".into(),
+                quote: "quote_1".into(),
+                price: "2".into(),
+                size: Uint128::new(100),
+                auto_match: None,
+                time_in_force: Some(TimeInForce::GoodTillTime { expires }),
+            },
+        ) {
+            panic!("unexpected error: {:?}", error)
         }
-    };
 
-    // determine refunds to bidder
-    if execute_price.lt(&bid_price) {
-        // calculate gross proceeds using bid price, (price * size), error if overflows
-        let original_gross_proceeds = bid_price
-            .checked_mul(Decimal::from(execute_size.u128()))
-            .ok_or(ContractError::TotalOverflow)?;
+        // nothing is swept before expiry
+        let sweep_response = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::SweepExpired { limit: None },
+        )
+        .unwrap();
+        assert!(sweep_response.messages.is_empty());
+
+        // once expired, anyone can sweep the ask back to its owner
+        let mut env = mock_env();
+        env.block.time = expires;
+        let sweep_response = execute(
+            deps.as_mut(),
+            env,
+            mock_info("anyone", &[]),
+            ExecuteMsg::SweepExpired { limit: None },
+        )
+        .unwrap();
+        assert_eq!(
+            sweep_response.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: "asker".into(),
+                amount: coins(100, "base_1"),
+            })]
+        );
+        assert!(sweep_response
+            .attributes
+            .contains(&attr("expired_asks", "1")));
+        assert!(ASKS_V1.may_load(&deps.storage, b"ask_1").unwrap().is_none());
+        assert!(ASK_EXPIRIES
+            .keys(&deps.storage, None, None, Order::Ascending)
+            .next()
+            .is_none());
+    }
+
+    #[test]
+    fn immediate_bids_do_not_rest_on_the_book() {
+        // setup
+        let mut deps = mock_provenance_dependencies();
+        setup_test_base(
+            &mut deps.storage,
+            &ContractInfoV3 {
+                name: "contract_name".into(),
+                bind_name: "".into(),
+                base_denom: "base_1".into(),
+                convertible_base_denoms: vec![],
+                supported_quote_denoms: vec!["quote_1".into()],
+                approvers: vec![],
+                executors: vec![Addr::unchecked("exec_1")],
+                ask_fee_info: None,
+                bid_fee_info: None,
+                ask_required_attributes: vec![],
+                bid_required_attributes: vec![],
+                price_precision: Uint128::new(2),
+                size_increment: Uint128::new(100),
+            },
+        );
 
-        // error if gross proceeds is not an integer
-        if original_gross_proceeds.fract().ne(&Decimal::zero()) {
-            return Err(ContractError::NonIntegerTotal);
+        // a resting ask for 100 at 2
+        if let Err(error) = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("asker", &coins(100, "base_1")),
+            ExecuteMsg::CreateAsk {
+                id: "ask_1".into(),
+                base: "base_1".into(),
+                quote: "quote_1".into(),
+                price: "2".into(),
+                size: Uint128::new(100),
+                auto_match: None,
+                time_in_force: None,
+            },
+        ) {
+            panic!("unexpected error: {:?}", error)
         }
 
-        // calculate refund
-        let bid_quote_refund = original_gross_proceeds
-            .checked_sub(actual_gross_proceeds)
-            .ok_or(ContractError::TotalOverflow)?
-            .to_u128()
-            .ok_or(ContractError::NonIntegerTotal)?;
-
-        // calculate fee based on original gross proceeds
-        let bid_fee_refund = {
-            let original_bid_fee = bid_order.calculate_fee(Uint128::new(
-                original_gross_proceeds
-                    .to_u128()
-                    .ok_or(ContractError::TotalOverflow)?,
-            ))?;
-
-            match (&actual_bid_fee, original_bid_fee) {
-                (Some(actual_bid_fee), Some(mut original_bid_fee)) => {
-                    let refund_amount = original_bid_fee.amount - actual_bid_fee.amount;
-
-                    if refund_amount.gt(&Uint128::zero()) {
-                        original_bid_fee.amount = refund_amount;
-                        Some(original_bid_fee)
-                    } else {
-                        None
-                    }
-                }
-                (_, _) => None,
-            }
-        };
-
-        if bid_quote_refund.gt(&0u128) {
-            response = add_transfer(
-                response,
-                is_quote_restricted_marker.to_owned(),
-                bid_quote_refund,
-                bid_order.quote.denom.to_owned(),
-                bid_order.owner.to_owned(),
-                env.contract.address.to_owned(),
-                env.contract.address.to_owned(),
-            );
-            if let Some(fee_refund) = &bid_fee_refund {
-                response = add_transfer(
-                    response,
-                    is_quote_restricted_marker,
-                    fee_refund.amount.u128(),
-                    fee_refund.denom.to_owned(),
-                    bid_order.owner.to_owned(),
-                    env.contract.address.to_owned(),
-                    env.contract.address,
+        // an immediate or cancel bid for 200 fills 100 and is refunded the rest
+        let create_bid_response = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bidder", &coins(400, "quote_1")),
+            ExecuteMsg::CreateBid {
+                id: "bid_ioc".into(),
+                base: "base_1".into(),
+                fee: None,
+                price: "2".into(),
+                quote: "quote_1".into(),
+                quote_size: Uint128::new(400),
+                size: Uint128::new(200),
+                auto_match: Some(true),
+                time_in_force: Some(TimeInForce::ImmediateOrCancel),
+            },
+        );
+        match create_bid_response {
+            Ok(response) => {
+                assert_eq!(
+                    response.messages,
+                    vec![
+                        SubMsg::new(BankMsg::Send {
+                            to_address: "asker".into(),
+                            amount: coins(200, "quote_1"),
+                        }),
+                        SubMsg::new(BankMsg::Send {
+                            to_address: "bidder".into(),
+                            amount: coins(100, "base_1"),
+                        }),
+                        SubMsg::new(BankMsg::Send {
+                            to_address: "bidder".into(),
+                            amount: coins(200, "quote_1"),
+                        }),
+                    ]
                 );
+                assert!(response
+                    .attributes
+                    .contains(&attr("action", ContractAction::ExpireBid.to_string())));
             }
+            Err(error) => panic!("unexpected error: {:?}", error),
+        }
+        assert!(BIDS_V3
+            .may_load(&deps.storage, b"bid_ioc")
+            .unwrap()
+            .is_none());
+        assert!(BID_POSITIONS
+            .may_load(&deps.storage, b"bid_ioc")
+            .unwrap()
+            .is_none());
+
+        // a fill or kill bid for 200 against 100 of resting asks fails
+        if let Err(error) = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("asker", &coins(100, "base_1")),
+            ExecuteMsg::CreateAsk {
+                id: "ask_2".into(),
+                base: "base_1".into(),
+                quote: "quote_1".into(),
+                price: "2".into(),
+                size: Uint128::new(100),
+                auto_match: None,
+                time_in_force: None,
+            },
+        ) {
+            panic!("unexpected error: {:?}", error)
         }
 
-        bid_order.update_remaining_amounts(&Action::Fill {
-            base: Coin {
-                denom: bid_order.base.denom.to_owned(),
-                amount: execute_size,
+        let create_bid_response = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bidder", &coins(400, "quote_1")),
+            ExecuteMsg::CreateBid {
+                id: "bid_fok".into(),
+                base: "base_1".into(),
+                fee: None,
+                price: "2".into(),
+                quote: "quote_1".into(),
+                quote_size: Uint128::new(400),
+                size: Uint128::new(200),
+                auto_match: Some(true),
+                time_in_force: Some(TimeInForce::FillOrKill),
             },
-            fee: actual_bid_fee,
-            price,
-            quote: Coin {
-                denom: bid_order.quote.denom.to_owned(),
-                amount: Uint128::new(
-                    actual_gross_proceeds
-                        .to_u128()
-                        .ok_or(ContractError::TotalOverflow)?,
-                ),
+        );
+        match create_bid_response {
+            Err(ContractError::FillOrKillNotFilled) => (),
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn update_ask_moves_escrow_and_queue_position() {
+        // setup
+        let mut deps = mock_provenance_dependencies();
+        setup_test_base(
+            &mut deps.storage,
+            &ContractInfoV3 {
+                name: "contract_name".into(),
+                bind_name: "".into(),
+                base_denom: "base_1".into(),
+                convertible_base_denoms: vec![],
+                supported_quote_denoms: vec!["quote_1".into()],
+                approvers: vec![],
+                executors: vec![Addr::unchecked("exec_1")],
+                ask_fee_info: None,
+                bid_fee_info: None,
+                ask_required_attributes: vec![],
+                bid_required_attributes: vec![],
+                price_precision: Uint128::new(2),
+                size_increment: Uint128::new(100),
             },
-        })?;
+        );
 
-        bid_order.update_remaining_amounts(&Action::Refund {
-            fee: bid_fee_refund,
-            quote: Coin {
-                denom: bid_order.quote.denom.to_owned(),
-                amount: Uint128::new(bid_quote_refund),
+        // create two asks for 200 at 2 and a newer one for 200 at 1.5
+        for (id, price) in [("ask_1", "2"), ("ask_2", "2"), ("ask_3", "1.5")] {
+            if let Err(error) = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("asker", &coins(200, "base_1")),
+                ExecuteMsg::CreateAsk {
+                    id: id.into(),
+                    base: "base_1".into(),
+                    quote: "quote_1".into(),
+                    price: price.into(),
+                    size: Uint128::new(200),
+                    auto_match: None,
+                    time_in_force: None,
+                },
+            ) {
+                panic!("unexpected error: {:?}", error)
+            }
+        }
+        let sequence =
+            |storage: &dyn Storage, id: &[u8]| ASK_POSITIONS.load(storage, id).unwrap().sequence;
+        let ask_1_sequence = sequence(&deps.storage, b"ask_1");
+
+        // shrinking the ask returns the base no longer offered and keeps its place in the queue
+        let update_ask_response = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("asker", &[]),
+            ExecuteMsg::UpdateAsk {
+                id: "ask_1".into(),
+                price: None,
+                size: Some(Uint128::new(100)),
             },
-        })?;
-    } else {
-        bid_order.update_remaining_amounts(&Action::Fill {
-            base: Coin {
-                denom: bid_order.base.denom.to_owned(),
-                amount: execute_size,
+        )
+        .unwrap();
+        assert_eq!(
+            update_ask_response.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: "asker".into(),
+                amount: coins(100, "base_1"),
+            })]
+        );
+        assert_eq!(
+            ASKS_V1.load(&deps.storage, b"ask_1").unwrap().size,
+            Uint128::new(100)
+        );
+        assert_eq!(sequence(&deps.storage, b"ask_1"), ask_1_sequence);
+
+        // growing the ask requires exactly the additional base
+        let update_ask_response = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("asker", &coins(200, "base_1")),
+            ExecuteMsg::UpdateAsk {
+                id: "ask_1".into(),
+                price: None,
+                size: Some(Uint128::new(200)),
             },
-            fee: actual_bid_fee,
-            price,
-            quote: Coin {
-                denom: bid_order.quote.denom.to_owned(),
-                amount: Uint128::new(
-                    actual_gross_proceeds
-                        .to_u128()
-                        .ok_or(ContractError::TotalOverflow)?,
-                ),
+        );
+        match update_ask_response {
+            Err(ContractError::SentFundsOrderMismatch) => (),
+            result => panic!("unexpected result: {:?}", result),
+        }
+
+        let update_ask_response = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("asker", &coins(100, "base_1")),
+            ExecuteMsg::UpdateAsk {
+                id: "ask_1".into(),
+                price: None,
+                size: Some(Uint128::new(200)),
             },
-        })?;
+        )
+        .unwrap();
+        assert!(update_ask_response.messages.is_empty());
+
+        // a larger ask moves behind every order created before the amendment
+        assert!(sequence(&deps.storage, b"ask_1") > sequence(&deps.storage, b"ask_3"));
+
+        // a new price moves the ask to the back of its new price level
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("asker", &[]),
+            ExecuteMsg::UpdateAsk {
+                id: "ask_2".into(),
+                price: Some("1.5".into()),
+                size: None,
+            },
+        )
+        .unwrap();
+        assert!(sequence(&deps.storage, b"ask_2") > sequence(&deps.storage, b"ask_3"));
+        let best_bid_ask = query_best_bid_ask(deps.as_ref(), mock_env(), "quote_1".into()).unwrap();
+        assert_eq!(best_bid_ask.best_ask.unwrap().id, "ask_3");
     }
 
-    // finally update or remove the orders from storage
-    if ask_order.size.is_zero() {
-        ASKS_V1.remove(deps.storage, ask_id.as_bytes());
-    } else {
-        ASKS_V1.update(deps.storage, ask_id.as_bytes(), |_| -> StdResult<_> {
-            Ok(ask_order)
-        })?;
-    }
+    #[test]
+    fn update_bid_moves_escrow_and_recomputes_fee() {
+        // setup
+        let mut deps = mock_provenance_dependencies();
+        setup_test_base(
+            &mut deps.storage,
+            &ContractInfoV3 {
+                name: "contract_name".into(),
+                bind_name: "".into(),
+                base_denom: "base_1".into(),
+                convertible_base_denoms: vec![],
+                supported_quote_denoms: vec!["quote_1".into()],
+                approvers: vec![],
+                executors: vec![Addr::unchecked("exec_1")],
+                ask_fee_info: None,
+                bid_fee_info: Some(FeeInfo {
+                    account: Addr::unchecked("fee_acct"),
+                    rate: "0.01".into(),
+                }),
+                ask_required_attributes: vec![],
+                bid_required_attributes: vec![],
+                price_precision: Uint128::new(2),
+                size_increment: Uint128::new(100),
+            },
+        );
 
-    if bid_order.get_remaining_base().eq(&Uint128::zero()) {
-        BIDS_V3.remove(deps.storage, bid_id.as_bytes());
-    } else {
-        BIDS_V3.update(deps.storage, bid_id.as_bytes(), |_| -> StdResult<_> {
-            Ok(bid_order)
-        })?;
-    }
+        // create a bid for 200 at 2 and a newer bid for 100 at 2
+        for (id, size, quote_size, fee) in [("bid_1", 200, 400, 4), ("bid_2", 100, 200, 2)] {
+            if let Err(error) = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("bidder", &coins(quote_size + fee, "quote_1")),
+                ExecuteMsg::CreateBid {
+                    id: id.into(),
+                    base: "base_1".into(),
+                    fee: Some(Coin::new(fee, "quote_1")),
+                    price: "2".into(),
+                    quote: "quote_1".into(),
+                    quote_size: Uint128::new(quote_size),
+                    size: Uint128::new(size),
+                    auto_match: None,
+                    time_in_force: None,
+                },
+            ) {
+                panic!("unexpected error: {:?}", error)
+            }
+        }
 
-    Ok(response)
-}
+        // reducing the size returns the excess quote and fee and keeps time priority
+        let update_bid_response = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bidder", &[]),
+            ExecuteMsg::UpdateBid {
+                id: "bid_1".into(),
+                price: None,
+                size: Some(Uint128::new(100)),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            update_bid_response.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: "bidder".into(),
+                amount: coins(202, "quote_1"),
+            })]
+        );
+        let best_bid_ask = query_best_bid_ask(deps.as_ref(), mock_env(), "quote_1".into()).unwrap();
+        assert_eq!(best_bid_ask.best_bid.unwrap().id, "bid_1");
+
+        // raising the price requires the additional quote and fee at the current rate
+        let update_bid_response = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bidder", &coins(100, "quote_1")),
+            ExecuteMsg::UpdateBid {
+                id: "bid_1".into(),
+                price: Some("3".into()),
+                size: None,
+            },
+        );
+        match update_bid_response {
+            Err(ContractError::SentFundsOrderMismatch) => (),
+            result => panic!("unexpected result: {:?}", result),
+        }
 
-// smart contract migrate/upgrade entrypoint
-#[entry_point]
-pub fn migrate(mut deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
-    msg.validate()?;
+        if let Err(error) = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bidder", &coins(101, "quote_1")),
+            ExecuteMsg::UpdateBid {
+                id: "bid_1".into(),
+                price: Some("3".into()),
+                size: None,
+            },
+        ) {
+            panic!("unexpected error: {:?}", error)
+        }
 
-    // build response
-    let mut response: Response = Response::new();
+        let bid_order = BIDS_V3.load(&deps.storage, b"bid_1").unwrap();
+        assert_eq!(bid_order.price, "3");
+        assert_eq!(bid_order.get_remaining_base(), Uint128::new(100));
+        assert_eq!(bid_order.get_remaining_quote(), Uint128::new(300));
+        assert_eq!(bid_order.fee, Some(Coin::new(3, "quote_1")));
+    }
 
-    // migrate contract_info
-    migrate_contract_info(deps.branch(), &msg)?;
+    #[test]
+    fn update_order_price_cannot_cross_the_book() {
+        // setup
+        let mut deps = mock_provenance_dependencies();
+        setup_test_base(
+            &mut deps.storage,
+            &ContractInfoV3 {
+                name: "contract_name".into(),
+                bind_name: "".into(),
+                base_denom: "base_1".into(),
+                convertible_base_denoms: vec![],
+                supported_quote_denoms: vec!["quote_1".into()],
+                approvers: vec![],
+                executors: vec![Addr::unchecked("exec_1")],
+                ask_fee_info: None,
+                bid_fee_info: None,
+                ask_required_attributes: vec![],
+                bid_required_attributes: vec![],
+                price_precision: Uint128::new(2),
+                size_increment: Uint128::new(100),
+            },
+        );
 
-    // migrate ask orders
-    migrate_ask_orders(deps.branch(), &msg)?;
+        // create an ask for 200 at 2 and a bid from another account for 200 at 1
+        if let Err(error) = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("asker", &coins(200, "base_1")),
+            ExecuteMsg::CreateAsk {
+                id: "ask_1".into(),
+                base: "base_1".into(),
+                quote: "quote_1".into(),
+                price: "2".into(),
+                size: Uint128::new(200),
+                auto_match: None,
+                time_in_force: None,
+            },
+        ) {
+            panic!("unexpected error: {:?}", error)
+        }
+        if let Err(error) = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bidder", &coins(200, "quote_1")),
+            ExecuteMsg::CreateBid {
+                id: "bid_1".into(),
+                base: "base_1".into(),
+                fee: None,
+                price: "1".into(),
+                quote: "quote_1".into(),
+                quote_size: Uint128::new(200),
+                size: Uint128::new(200),
+                auto_match: None,
+                time_in_force: None,
+            },
+        ) {
+            panic!("unexpected error: {:?}", error)
+        }
 
-    // migrate bid orders
-    response = migrate_bid_orders(deps.branch(), env, &msg, response)?;
+        // lowering the ask onto the bid is rejected
+        let update_ask_response = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("asker", &[]),
+            ExecuteMsg::UpdateAsk {
+                id: "ask_1".into(),
+                price: Some("1".into()),
+                size: None,
+            },
+        );
+        match update_ask_response {
+            Err(ContractError::InvalidFields { fields }) => {
+                assert_eq!(fields, vec![String::from("price")])
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
 
-    // lastly, migrate version_info
-    migrate_version_info(deps.branch())?;
+        // raising the bid onto the ask is rejected
+        let update_bid_response = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bidder", &coins(200, "quote_1")),
+            ExecuteMsg::UpdateBid {
+                id: "bid_1".into(),
+                price: Some("2".into()),
+                size: None,
+            },
+        );
+        match update_bid_response {
+            Err(ContractError::InvalidFields { fields }) => {
+                assert_eq!(fields, vec![String::from("price")])
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
 
-    Ok(response)
-}
+        // a new price that leaves the book uncrossed is accepted
+        if let Err(error) = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("asker", &[]),
+            ExecuteMsg::UpdateAsk {
+                id: "ask_1".into(),
+                price: Some("1.5".into()),
+                size: None,
+            },
+        ) {
+            panic!("unexpected error: {:?}", error)
+        }
+        assert_eq!(ASKS_V1.load(&deps.storage, b"ask_1").unwrap().price, "1.5");
+    }
 
-// smart contract query entrypoint
-#[entry_point]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    msg.validate()?;
+    #[test]
+    fn fee_schedule_charges_maker_and_taker_rates() {
+        // setup
+        let mut deps = mock_provenance_dependencies();
+        setup_test_base(
+            &mut deps.storage,
+            &ContractInfoV3 {
+                name: "contract_name".into(),
+                bind_name: "".into(),
+                base_denom: "base_1".into(),
+                convertible_base_denoms: vec![],
+                supported_quote_denoms: vec!["quote_1".into()],
+                approvers: vec![],
+                executors: vec![Addr::unchecked("exec_1")],
+                ask_fee_info: None,
+                bid_fee_info: None,
+                ask_required_attributes: vec![],
+                bid_required_attributes: vec![],
+                price_precision: Uint128::new(2),
+                size_increment: Uint128::new(100),
+            },
+        );
+        mock_contract_admin(&mut deps.querier.mock_querier, "admin");
+
+        let fee_schedule = FeeSchedule {
+            account: Addr::unchecked("fee_acct"),
+            maker_rate: "0.01".into(),
+            taker_rate: "0.02".into(),
+            tiers: vec![FeeTier {
+                min_volume: Uint128::new(100),
+                maker_rate: "0".into(),
+                taker_rate: "0.01".into(),
+            }],
+            discounts: vec![],
+        };
 
-    match msg {
-        QueryMsg::GetAsk { id } => {
-            return to_binary(&ASKS_V1.load(deps.storage, id.as_bytes())?);
+        // only the contract admin may set the fee schedule
+        let set_fee_schedule_response = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("asker", &[]),
+            ExecuteMsg::SetFeeSchedule {
+                fee_schedule: Some(fee_schedule.clone()),
+            },
+        );
+        match set_fee_schedule_response {
+            Err(ContractError::Unauthorized) => (),
+            result => panic!("unexpected result: {:?}", result),
         }
-        QueryMsg::GetBid { id } => {
-            return to_binary(&BIDS_V3.load(deps.storage, id.as_bytes())?);
+        assert!(FEE_SCHEDULE.may_load(&deps.storage).unwrap().is_none());
+
+        if let Err(error) = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin", &[]),
+            ExecuteMsg::SetFeeSchedule {
+                fee_schedule: Some(fee_schedule),
+            },
+        ) {
+            panic!("unexpected error: {:?}", error)
         }
-        QueryMsg::GetContractInfo {} => to_binary(&get_contract_info(deps.storage)?),
-        QueryMsg::GetVersionInfo {} => to_binary(&get_version_info(deps.storage)?),
-    }
-}
 
-// unit tests
-#[cfg(test)]
-mod tests {
-    use cosmwasm_std::testing::mock_env;
-    use cosmwasm_std::{Addr, Storage, Uint128};
+        // create a resting ask
+        if let Err(error) = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("asker", &coins(100, "base_1")),
+            ExecuteMsg::CreateAsk {
+                id: "ask_1".into(),
+                base: "base_1".into(),
+                quote: "quote_1".into(),
+                price: "2".into(),
+                size: Uint128::new(100),
+                auto_match: None,
+                time_in_force: None,
+            },
+        ) {
+            panic!("unexpected error: {:?}", error)
+        }
 
-    use super::*;
-    use provwasm_mocks::mock_provenance_dependencies;
+        // the crossing bid escrows its fee at the higher taker rate
+        let create_bid_response = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bidder", &coins(204, "quote_1")),
+            ExecuteMsg::CreateBid {
+                id: "bid_1".into(),
+                base: "base_1".into(),
+                fee: Some(Coin::new(4, "quote_1")),
+                price: "2".into(),
+                quote: "quote_1".into(),
+                quote_size: Uint128::new(200),
+                size: Uint128::new(100),
+                auto_match: Some(true),
+                time_in_force: None,
+            },
+        );
+
+        // the resting ask pays the maker rate and the incoming bid the taker rate
+        match create_bid_response {
+            Ok(response) => {
+                assert_eq!(
+                    response.messages,
+                    vec![
+                        SubMsg::new(BankMsg::Send {
+                            to_address: "fee_acct".into(),
+                            amount: coins(2, "quote_1"),
+                        }),
+                        SubMsg::new(BankMsg::Send {
+                            to_address: "fee_acct".into(),
+                            amount: coins(4, "quote_1"),
+                        }),
+                        SubMsg::new(BankMsg::Send {
+                            to_address: "asker".into(),
+                            amount: coins(198, "quote_1"),
+                        }),
+                        SubMsg::new(BankMsg::Send {
+                            to_address: "bidder".into(),
+                            amount: coins(100, "base_1"),
+                        }),
+                    ]
+                );
+            }
+            Err(error) => panic!("unexpected error: {:?}", error),
+        }
+
+        // the fill moves the asker into the next volume tier
+        let fee_rates = query_fee_rates(deps.as_ref(), mock_env(), "asker".into()).unwrap();
+        assert_eq!(fee_rates.volume, Uint128::new(100));
+        assert_eq!(fee_rates.maker_rate, Some("0".into()));
+        assert_eq!(fee_rates.taker_rate, Some("0.01".into()));
+
+        // volume outside the trailing window no longer counts
+        let mut env = mock_env();
+        env.block.time = env
+            .block
+            .time
+            .plus_seconds(VOLUME_WINDOW_DAYS * SECONDS_PER_DAY);
+        let fee_rates = query_fee_rates(deps.as_ref(), env, "asker".into()).unwrap();
+        assert_eq!(fee_rates.volume, Uint128::zero());
+        assert_eq!(fee_rates.maker_rate, Some("0.01".into()));
+    }
 
     #[test]
-    fn query_contract_info() {
+    fn fee_schedule_discounts_by_account_attribute() {
         // setup
         let mut deps = mock_provenance_dependencies();
         setup_test_base(
@@ -1453,33 +4170,117 @@ mod tests {
             &ContractInfoV3 {
                 name: "contract_name".into(),
                 bind_name: "".into(),
-                base_denom: "base_denom".into(),
-                convertible_base_denoms: vec!["con_base_1".into(), "con_base_2".into()],
-                supported_quote_denoms: vec!["quote_1".into(), "quote_2".into()],
-                approvers: vec![Addr::unchecked("exec_1"), Addr::unchecked("exec_2")],
-                executors: vec![Addr::unchecked("exec_1"), Addr::unchecked("exec_2")],
+                base_denom: "base_1".into(),
+                convertible_base_denoms: vec![],
+                supported_quote_denoms: vec!["quote_1".into()],
+                approvers: vec![],
+                executors: vec![Addr::unchecked("exec_1")],
                 ask_fee_info: None,
                 bid_fee_info: None,
-                ask_required_attributes: vec!["ask_tag_1".into(), "ask_tag_2".into()],
-                bid_required_attributes: vec!["bid_tag_1".into(), "bid_tag_2".into()],
+                ask_required_attributes: vec![],
+                bid_required_attributes: vec![],
                 price_precision: Uint128::new(2),
                 size_increment: Uint128::new(100),
             },
         );
+        mock_contract_admin(&mut deps.querier.mock_querier, "admin");
+
+        // rates above 1 are rejected
+        let set_fee_schedule_response = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin", &[]),
+            ExecuteMsg::SetFeeSchedule {
+                fee_schedule: Some(FeeSchedule {
+                    account: Addr::unchecked("fee_acct"),
+                    maker_rate: "0.01".into(),
+                    taker_rate: "0.03".into(),
+                    tiers: vec![],
+                    discounts: vec![FeeDiscount {
+                        attribute: "fee.discount.pb".into(),
+                        rate: "1.5".into(),
+                    }],
+                }),
+            },
+        );
+        match set_fee_schedule_response {
+            Err(ContractError::InvalidFields { fields }) => {
+                assert_eq!(fields, vec![String::from("fee_schedule")])
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
 
-        // query for contract_info
-        let query_contract_info_response =
-            query(deps.as_ref(), mock_env(), QueryMsg::GetContractInfo {});
+        // discount holders of one attribute by half, and holders of another by 90%
+        if let Err(error) = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin", &[]),
+            ExecuteMsg::SetFeeSchedule {
+                fee_schedule: Some(FeeSchedule {
+                    account: Addr::unchecked("fee_acct"),
+                    maker_rate: "0.01".into(),
+                    taker_rate: "0.03".into(),
+                    tiers: vec![],
+                    discounts: vec![
+                        FeeDiscount {
+                            attribute: "fee.discount.pb".into(),
+                            rate: "0.5".into(),
+                        },
+                        FeeDiscount {
+                            attribute: "fee.other.pb".into(),
+                            rate: "0.9".into(),
+                        },
+                    ],
+                }),
+            },
+        ) {
+            panic!("unexpected error: {:?}", error)
+        }
 
-        match query_contract_info_response {
-            Ok(contract_info) => {
-                assert_eq!(
-                    contract_info,
-                    to_binary(&get_contract_info(&deps.storage).unwrap()).unwrap()
-                )
-            }
-            Err(error) => panic!("unexpected error: {:?}", error),
+        // the account only holds the first attribute
+        QueryAttributesRequest::mock_response(
+            &mut deps.querier,
+            QueryAttributesResponse {
+                account: "trader".to_string(),
+                attributes: vec![Attribute {
+                    name: "fee.discount.pb".to_string(),
+                    value: "ok".as_bytes().to_vec(),
+                    attribute_type: AttributeType::String.into(),
+                    address: "".to_string(),
+                }],
+                pagination: None,
+            },
+        );
+
+        let fee_rates = query_fee_rates(deps.as_ref(), mock_env(), "trader".into()).unwrap();
+        assert_eq!(fee_rates.maker_rate, Some("0.005".into()));
+        assert_eq!(fee_rates.taker_rate, Some("0.015".into()));
+
+        // clearing the schedule falls back to the flat rates
+        if let Err(error) = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin", &[]),
+            ExecuteMsg::SetFeeSchedule { fee_schedule: None },
+        ) {
+            panic!("unexpected error: {:?}", error)
         }
+        assert!(FEE_SCHEDULE.may_load(&deps.storage).unwrap().is_none());
+    }
+
+    // answer contract info queries with the given wasm admin
+    fn mock_contract_admin(querier: &mut MockQuerier, admin: &str) {
+        let admin = admin.to_string();
+        querier.update_wasm(move |query| match query {
+            WasmQuery::ContractInfo { .. } => {
+                let mut contract_info = ContractInfoResponse::new(1, "creator");
+                contract_info.admin = Some(admin.to_owned());
+                SystemResult::Ok(ContractResult::Ok(to_binary(&contract_info).unwrap()))
+            }
+            _ => SystemResult::Err(SystemError::UnsupportedRequest {
+                kind: "wasm".into(),
+            }),
+        });
     }
 
     fn setup_test_base(storage: &mut dyn Storage, contract_info: &ContractInfoV3) {